@@ -1,12 +1,17 @@
 use std::string::ToString;
-use svg::node::element::Group;
+use svg::node::element::{Group, Line};
 use svg::parser::Error;
 use svg::Node;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
 use crate::{Scale, Chart};
-use crate::components::axis::{AxisLine, AxisTick};
 use crate::scales::ScaleType;
+use crate::components::axis::{AxisLine, AxisTick};
+use crate::components::TextMeasurer;
+use crate::views::view_class;
+
+/// The dash pattern of the gridlines halfway between the ticks.
+const MINOR_GRIDLINE_DASHARRAY: &str = "2,2";
 
 /// Enum of possible axis positions on the chart.
 #[derive(Copy, Clone, PartialEq)]
@@ -25,40 +30,62 @@ pub struct Axis {
     label: String,
     label_rotation: isize,
     label_format: String,
+    label_prefix: String,
+    label_suffix: String,
     length: isize,
+    domain_extent: Option<(f32, f32)>,
+    domain_line_trim: bool,
+    scale_type: ScaleType,
+    domain_limits: Option<(f32, f32)>,
+    categories: Vec<String>,
+    gridlines: bool,
+    minor_gridlines: bool,
+    gridline_length: isize,
+    class: Option<String>,
 }
 
 impl Axis {
     /// Create a new instance of an axis for a chart based on the provided scale and position.
-    fn new<'a, T: ToString>(scale: &'a dyn Scale<T>, position: AxisPosition, chart: &Chart<'a>) -> Self {
+    fn new<T: ToString>(scale: &dyn Scale<T>, position: AxisPosition, chart: &Chart<'_>) -> Self {
         Self {
-            ticks: Self::generate_ticks(scale, position),
+            ticks: Self::generate_ticks(scale, position, chart),
             position,
             axis_line: Self::get_axis_line(position, chart),
             label: String::new(),
             label_rotation: 0,
             label_format: String::new(),
+            label_prefix: String::new(),
+            label_suffix: String::new(),
             length: Self::get_axis_length(position, chart),
+            domain_extent: Self::get_domain_extent(scale),
+            domain_line_trim: false,
+            scale_type: scale.get_type(),
+            domain_limits: scale.domain_limits(),
+            categories: Self::get_categories(scale),
+            gridlines: false,
+            minor_gridlines: false,
+            gridline_length: Self::get_gridline_length(position, chart),
+            class: None,
         }
     }
 
     /// Create a new axis at the top of the chart.
-    pub fn new_top_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_top_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart<'_>) -> Self {
         Self::new(scale, AxisPosition::Top, chart)
     }
 
     /// Create a new axis to the right of the chart.
-    pub fn new_right_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_right_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart<'_>) -> Self {
         Self::new(scale, AxisPosition::Right, chart)
     }
 
     /// Create a new axis at the bottom of the chart.
-    pub fn new_bottom_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_bottom_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart<'_>) -> Self {
         Self::new(scale, AxisPosition::Bottom, chart)
     }
 
     /// Create a new axis to the left of the chart.
-    pub fn new_left_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_left_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart<'_>) -> Self {
         Self::new(scale, AxisPosition::Left, chart)
     }
 
@@ -67,6 +94,11 @@ impl Axis {
         self.label = label;
     }
 
+    /// Set a class to add to the group of the axis, e.g. to style it with an external stylesheet.
+    pub fn set_class(&mut self, class: &str) {
+        self.class = Some(class.to_owned());
+    }
+
     /// Set tick label rotation.
     pub fn set_tick_label_rotation(&mut self, rotation: isize) {
         self.label_rotation = rotation;
@@ -80,11 +112,64 @@ impl Axis {
         self.ticks.iter_mut().for_each(|tick| tick.set_label_format(label_format));
     }
 
+    /// Set the text to display before each tick label, e.g. a currency symbol.
+    pub fn set_tick_label_prefix(&mut self, prefix: &str) {
+        self.label_prefix = String::from(prefix);
+        self.update_tick_label_affixes();
+    }
+
+    /// Set the text to display after each tick label, e.g. a unit.
+    pub fn set_tick_label_suffix(&mut self, suffix: &str) {
+        self.label_suffix = String::from(suffix);
+        self.update_tick_label_affixes();
+    }
+
+    fn update_tick_label_affixes(&mut self) {
+        let (prefix, suffix) = (self.label_prefix.as_str(), self.label_suffix.as_str());
+        self.ticks.iter_mut().for_each(|tick| tick.set_label_affixes(prefix, suffix));
+    }
+
+    /// Set whether the axis line should span only from the first to the last band,
+    /// excluding the outer padding. This has no effect on scales without bands.
+    pub fn set_domain_line_trim(&mut self, trim: bool) {
+        self.domain_line_trim = trim;
+    }
+
+    /// Set whether a solid gridline is drawn across the view at each tick.
+    pub fn set_gridlines(&mut self, enabled: bool) {
+        self.gridlines = enabled;
+    }
+
+    /// Set whether a dashed, lighter gridline is drawn across the view halfway between each pair of ticks.
+    pub fn set_minor_gridlines(&mut self, enabled: bool) {
+        self.minor_gridlines = enabled;
+    }
+
+    /// Return the type of the scale that the axis represents.
+    pub fn get_scale_type(&self) -> ScaleType {
+        self.scale_type
+    }
+
+    /// Return the limits of the domain of the scale that the axis represents, if it is numeric.
+    pub fn get_domain_limits(&self) -> Option<(f32, f32)> {
+        self.domain_limits
+    }
+
+    /// Return the categories of the band scale that the axis represents, or none for other scales.
+    pub fn get_category_domain(&self) -> &Vec<String> {
+        &self.categories
+    }
+
     /// Return whether the axis has a label or not.
     pub fn has_label(&self) -> bool {
         self.label.len() > 0
     }
 
+    /// Return the width in pixels that each formatted tick label needs when rendered.
+    pub fn tick_label_widths(&self, measurer: &dyn TextMeasurer) -> Vec<f32> {
+        self.ticks.iter().map(|tick| measurer.measure(&tick.formatted_label())).collect()
+    }
+
     /// Compute the length of the axis.
     fn get_axis_length<'a>(position: AxisPosition, chart: &Chart<'a>) -> isize {
         if position == AxisPosition::Top || position == AxisPosition::Bottom {
@@ -94,6 +179,59 @@ impl Axis {
         }
     }
 
+    /// Compute the length of the gridlines, which span the view across the axis.
+    fn get_gridline_length<'a>(position: AxisPosition, chart: &Chart<'a>) -> isize {
+        match position {
+            AxisPosition::Top => chart.get_view_height(),
+            AxisPosition::Bottom => -chart.get_view_height(),
+            AxisPosition::Left => chart.get_view_width(),
+            AxisPosition::Right => -chart.get_view_width(),
+        }
+    }
+
+    /// Generate svg for the gridlines at the ticks and, when enabled, halfway between them.
+    fn gridlines_to_svg(&self) -> Group {
+        let mut group = Group::new().set("class", "gridlines");
+        let offsets = self.ticks.iter().map(|tick| tick.get_tick_offset()).collect::<Vec<f32>>();
+
+        if self.gridlines {
+            for offset in offsets.iter() {
+                group.append(self.gridline("gridline-major", *offset).set("stroke", "#ccc"));
+            }
+        }
+        if self.minor_gridlines {
+            for pair in offsets.windows(2) {
+                group.append(
+                    self.gridline("gridline-minor", (pair[0] + pair[1]) / 2_f32)
+                        .set("stroke", "#eee")
+                        .set("stroke-dasharray", MINOR_GRIDLINE_DASHARRAY),
+                );
+            }
+        }
+
+        group
+    }
+
+    fn gridline(&self, class: &str, offset: f32) -> Line {
+        let (x2, y2) = match self.position {
+            AxisPosition::Top | AxisPosition::Bottom => (offset, self.gridline_length as f32),
+            AxisPosition::Left | AxisPosition::Right => (self.gridline_length as f32, offset),
+        };
+        let (x1, y1) = match self.position {
+            AxisPosition::Top | AxisPosition::Bottom => (offset, 0_f32),
+            AxisPosition::Left | AxisPosition::Right => (0_f32, offset),
+        };
+
+        Line::new()
+            .set("class", class)
+            .set("x1", x1)
+            .set("y1", y1)
+            .set("x2", x2)
+            .set("y2", y2)
+            .set("shape-rendering", "crispEdges")
+            .set("stroke-width", 1)
+    }
+
     /// Generate svg for the axis.
     pub fn to_svg(&self) -> Result<Group, Error> {
         let axis_class = match self.position {
@@ -103,9 +241,18 @@ impl Axis {
             AxisPosition::Right => "y-axis",
         };
 
-        let mut group = Group::new()
-            .set("class", axis_class)
-            .add(self.axis_line.to_svg().unwrap());
+        let axis_line = match self.domain_extent {
+            Some((start, end)) if self.domain_line_trim => self.axis_line.trimmed(start, end),
+            _ => self.axis_line.to_owned(),
+        };
+
+        let mut group = Group::new().set("class", view_class(axis_class, &self.class));
+
+        // Draw the gridlines first, so that the axis line and the ticks are drawn over them.
+        if self.gridlines || self.minor_gridlines {
+            group.append(self.gridlines_to_svg());
+        }
+        group.append(axis_line.to_svg().unwrap());
 
         for tick in self.ticks.iter() {
             group.append(tick.to_svg().unwrap());
@@ -134,7 +281,9 @@ impl Axis {
     }
 
     /// Generate ticks for the axis based on the scale and position.
-    fn generate_ticks<'a, T: ToString>(scale: &'a dyn Scale<T>, position: AxisPosition) -> Vec<AxisTick> {
+    /// When the chart sets a tick label width, only every so many ticks are kept, so that there are
+    /// no more ticks than `fit_tick_count` allows along the axis, but at least one.
+    fn generate_ticks<T: ToString>(scale: &dyn Scale<T>, position: AxisPosition, chart: &Chart<'_>) -> Vec<AxisTick> {
         let mut ticks = Vec::new();
         let label_offset = {
             if position == AxisPosition::Top || position == AxisPosition::Bottom {
@@ -144,24 +293,47 @@ impl Axis {
             }
         };
 
-        for tick in scale.get_ticks() {
-            let tick_offset = match position {
-                AxisPosition::Bottom if scale.get_type() == ScaleType::Band => scale.scale(&tick) + scale.bandwidth().unwrap() / 2_f32,
-                AxisPosition::Bottom => scale.scale(&tick),
-                AxisPosition::Left if scale.get_type() == ScaleType::Band => scale.scale(&tick) + scale.bandwidth().unwrap() / 2_f32,
-                AxisPosition::Left => scale.scale(&tick),
-                AxisPosition::Top if scale.get_type() == ScaleType::Band => scale.scale(&tick) + scale.bandwidth().unwrap() / 2_f32,
-                AxisPosition::Top => scale.scale(&tick),
-                AxisPosition::Right if scale.get_type() == ScaleType::Band => scale.scale(&tick) + scale.bandwidth().unwrap() / 2_f32,
-                AxisPosition::Right => scale.scale(&tick),
-            };
-            let axis_tick = AxisTick::new(tick_offset, label_offset, 0, tick.to_string(), position);
+        let labeled_ticks = scale.labeled_ticks();
+        let stride = match chart.get_tick_label_width() {
+            Some(avg_label_px) => {
+                let axis_pixels = Self::get_axis_length(position, chart).max(0) as usize;
+                let tick_count = scale.fit_tick_count(axis_pixels, avg_label_px).max(1);
+                labeled_ticks.len().div_ceil(tick_count)
+            }
+            None => 1,
+        };
+
+        for (tick_offset, label) in labeled_ticks.into_iter().step_by(stride.max(1)) {
+            let axis_tick = AxisTick::new(tick_offset, label_offset, 0, label, position);
             ticks.push(axis_tick);
         }
 
         ticks
     }
 
+    /// List the categories of a band scale, in the order of its domain.
+    fn get_categories<T: ToString>(scale: &dyn Scale<T>) -> Vec<String> {
+        match scale.get_type() {
+            ScaleType::Band => scale.get_ticks().iter().map(|tick| tick.to_string()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Compute the range covered by the bands of the scale, from the edge of the first band
+    /// to the edge of the last one.
+    fn get_domain_extent<T>(scale: &dyn Scale<T>) -> Option<(f32, f32)> {
+        let band_positions = scale.get_ticks().iter().map(|tick| scale.scale(tick)).collect::<Vec<f32>>();
+
+        if scale.get_type() != ScaleType::Band || band_positions.is_empty() {
+            return None;
+        }
+
+        let start = band_positions.iter().cloned().fold(f32::INFINITY, f32::min);
+        let end = band_positions.iter().cloned().fold(f32::NEG_INFINITY, f32::max) + scale.bandwidth().unwrap();
+
+        Some((start, end))
+    }
+
     /// Generate the line that represents the axis.
     fn get_axis_line<'a>(position: AxisPosition, chart: &Chart<'a>) -> AxisLine {
         match position {
@@ -172,3 +344,93 @@ impl Axis {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::AverageCharWidthMeasurer;
+    use crate::{ScaleBand, ScaleLinear};
+
+    #[test]
+    fn tick_label_widths_of_formatted_labels() {
+        let chart = Chart::new();
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 1_000_000_f32])
+            .set_range(vec![0, 700]);
+        let measurer = AverageCharWidthMeasurer::default();
+
+        let mut axis = Axis::new_bottom_axis(&scale, &chart);
+        let raw_widths = axis.tick_label_widths(&measurer);
+        assert_eq!(raw_widths.last(), Some(&measurer.measure("1000000")));
+
+        axis.set_tick_label_format(".1s");
+        let formatted_widths = axis.tick_label_widths(&measurer);
+        assert_eq!(formatted_widths.len(), raw_widths.len());
+        assert_eq!(formatted_widths.last(), Some(&measurer.measure("1M")));
+        assert!(formatted_widths.last() < raw_widths.last());
+    }
+
+    #[test]
+    fn tick_labels_with_prefix_and_suffix() {
+        let chart = Chart::new();
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 5_000_000_f32])
+            .set_range(vec![460, 0]);
+        let labels = |axis: &Axis| axis.ticks.iter().map(|tick| tick.formatted_label()).collect::<Vec<String>>();
+
+        let mut axis = Axis::new_left_axis(&scale, &chart);
+        axis.set_tick_label_prefix("$");
+        assert!(labels(&axis).contains(&String::from("$500000")));
+
+        axis.set_tick_label_format(".2s");
+        assert!(labels(&axis).contains(&String::from("$1.5M")));
+
+        axis.set_tick_label_prefix("");
+        axis.set_tick_label_suffix(" ms");
+        assert!(labels(&axis).contains(&String::from("1.5M ms")));
+        assert!(axis.to_svg().unwrap().to_string().contains(">\n1.5M ms\n</text>"));
+    }
+
+    #[test]
+    fn domain_line_trimmed_to_band_extent() {
+        let chart = Chart::new();
+        let scale = ScaleBand::new()
+            .set_domain(vec![String::from("A"), String::from("B"), String::from("C")])
+            .set_range(vec![0, 300])
+            .set_inner_padding(0_f32)
+            .set_outer_padding(0.5_f32);
+        let first_band_start = scale.scale(&String::from("A"));
+        let last_band_end = scale.scale(&String::from("C")) + scale.bandwidth().unwrap();
+        assert!(first_band_start > scale.range_start());
+
+        let mut axis = Axis::new_bottom_axis(&scale, &chart);
+        let full_line = axis.to_svg().unwrap().to_string();
+        assert!(full_line.contains(&format!("x1=\"0\" x2=\"{}\"", chart.get_view_width())));
+
+        axis.set_domain_line_trim(true);
+        let trimmed_line = axis.to_svg().unwrap().to_string();
+        assert!(trimmed_line.contains(&format!("x1=\"{}\" x2=\"{}\"", first_band_start, last_band_end)));
+    }
+
+    #[test]
+    fn narrow_axis_thins_out_its_ticks() {
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![0, 100]);
+        let tick_count = |chart: &Chart| Axis::new_bottom_axis(&scale, chart).to_svg().unwrap().to_string().matches("class=\"tick\"").count();
+
+        let chart = Chart::new().set_width(200).set_margins(10, 50, 10, 50);
+        assert_eq!(tick_count(&chart), 11);
+
+        // 100 pixels fit two labels of 40 pixels, so every sixth of the 11 ticks is kept.
+        let narrow = chart.set_tick_label_width(40);
+        assert_eq!(scale.fit_tick_count(narrow.get_view_width() as usize, 40), 2);
+        assert_eq!(tick_count(&narrow), 2);
+
+        let wide = Chart::new().set_width(900).set_margins(10, 50, 10, 50).set_tick_label_width(40);
+        assert_eq!(tick_count(&wide), 11);
+
+        let too_narrow = Chart::new().set_width(130).set_margins(10, 50, 10, 50).set_tick_label_width(40);
+        assert_eq!(tick_count(&too_narrow), 1);
+    }
+}
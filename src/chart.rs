@@ -1,11 +1,19 @@
 use crate::axis::AxisPosition;
+use crate::components::bar::BarLayout;
+use crate::components::categorised_bars::{CategorisedValues, ToF64};
 use crate::components::legend::LegendEntry;
+use crate::components::IdScope;
 use crate::legend::Legend;
+use crate::scales::ScaleType;
+use crate::scales::nice_step;
 use crate::views::View;
-use crate::{Axis, Scale};
+use crate::{AnnotationView, Axis, HorizontalBarView, Scale, ScaleBand, ScaleLinear, VerticalBarView};
 use std::ffi::OsStr;
+use std::fmt::Display;
 use std::fs::File;
+use std::hash::Hash;
 use std::io::Write;
+use std::ops::AddAssign;
 use std::path::Path;
 use std::string::ToString;
 use svg;
@@ -13,6 +21,16 @@ use svg::node::element::Group;
 use svg::node::element::Text;
 use svg::node::Text as TextNode;
 use svg::Node;
+use crate::ChartError;
+
+/// The width of the view suggested for each category when sizing a chart to its data.
+const SUGGESTED_CATEGORY_WIDTH: usize = 40;
+/// The smallest width of the view suggested when sizing a chart to its data.
+const MIN_SUGGESTED_VIEW_WIDTH: usize = 400;
+/// The extra width suggested to make room for a legend next to the view.
+const SUGGESTED_LEGEND_WIDTH: usize = 150;
+/// The bounds of the suggested chart height.
+const SUGGESTED_HEIGHT_RANGE: (usize, usize) = (400, 600);
 
 /// Define the orientation enum to aid in rendering and business logic.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -21,6 +39,46 @@ pub enum Orientation {
     Vertical,
 }
 
+/// The bars of categorised values that the chart lays out itself, with the segments as keys.
+struct CategorisedBars {
+    categories: Vec<String>,
+    keys: Vec<String>,
+    /// The category, value and key of each segment of each category.
+    data: Vec<(String, f32, String)>,
+}
+
+/// What the chart keeps of the scale of an axis to place its annotations: the middle of
+/// each band of a band scale, or else the scale itself if it is numeric.
+struct AnnotationScale<'s> {
+    band_centers: Vec<(String, f32)>,
+    numeric: Option<&'s dyn Scale<f32>>,
+}
+
+impl<'s> AnnotationScale<'s> {
+    fn new<T: ToString>(scale: &'s dyn Scale<T>) -> Self {
+        let band_centers = match scale.get_type() {
+            ScaleType::Band => scale
+                .get_ticks()
+                .iter()
+                .map(|tick| (tick.to_string(), scale.scale(tick) + scale.bandwidth().unwrap() / 2_f32))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Self { band_centers, numeric: scale.as_numeric() }
+    }
+
+    /// The middle of the band of the category, if the scale has a band for it.
+    fn category_position(&self, category: &str) -> Option<f32> {
+        self.band_centers.iter().find(|(band, _)| band == category).map(|(_, center)| *center)
+    }
+
+    /// The position of the value, if the scale is numeric.
+    fn value_position(&self, value: f32) -> Option<f32> {
+        self.numeric.map(|scale| scale.scale(&value))
+    }
+}
+
 /// The Chart struct definition.
 /// A Chart is the smallest entity that can be saved (the bigger one is a Page (TBD)).
 pub struct Chart<'a> {
@@ -34,9 +92,19 @@ pub struct Chart<'a> {
     x_axis_bottom: Option<Axis>,
     y_axis_left: Option<Axis>,
     y_axis_right: Option<Axis>,
+    axis_scales: Vec<(AxisPosition, AnnotationScale<'a>)>,
     legend_position: Option<AxisPosition>,
+    legend_swatch_borders: bool,
+    legend_class: Option<String>,
+    tick_label_width: Option<usize>,
     views: Vec<&'a dyn View<'a>>,
+    categorised_bars: Option<CategorisedBars>,
+    transposed: bool,
+    /// The category, value, text and leader line length of each annotation.
+    annotations: Vec<(String, f32, String, Option<f32>)>,
+    annotation_leader_line_length: Option<f32>,
     title: String,
+    description: String,
 }
 
 impl<'a> Chart<'a> {
@@ -53,9 +121,48 @@ impl<'a> Chart<'a> {
             x_axis_bottom: None,
             y_axis_left: None,
             y_axis_right: None,
+            axis_scales: Vec::new(),
             legend_position: None,
+            legend_swatch_borders: false,
+            legend_class: None,
+            tick_label_width: None,
             views: Vec::new(),
+            categorised_bars: None,
+            transposed: false,
+            annotations: Vec::new(),
+            annotation_leader_line_length: None,
             title: String::new(),
+            description: String::new(),
+        }
+    }
+
+    /// Suggest a chart size `(width, height)` for the given amount of categories, which gets
+    /// wider as there are more categories and leaves room for a legend if needed. The height
+    /// follows a 4:3 aspect ratio of the width without the legend, within reasonable bounds.
+    pub fn suggested_size(category_count: usize, has_legend: bool) -> (usize, usize) {
+        let default_margins = Self::new();
+        let margins = (default_margins.margin_left + default_margins.margin_right) as usize;
+        let view_width = (category_count * SUGGESTED_CATEGORY_WIDTH).max(MIN_SUGGESTED_VIEW_WIDTH);
+        let legend_width = if has_legend { SUGGESTED_LEGEND_WIDTH } else { 0 };
+        let height = (view_width * 3 / 4).clamp(SUGGESTED_HEIGHT_RANGE.0, SUGGESTED_HEIGHT_RANGE.1);
+
+        (margins + view_width + legend_width, height)
+    }
+
+    /// The largest size `(width, height)` within the bounds that has the given width to height ratio,
+    /// e.g. `16_f32 / 9_f32`. A ratio that is not positive leaves the bounds as they are.
+    pub fn fit_aspect(max_width: usize, max_height: usize, ratio: f32) -> (usize, usize) {
+        if !(ratio > 0_f32 && ratio.is_finite()) {
+            return (max_width, max_height);
+        }
+
+        let ratio = ratio as f64;
+        // Round to the nearest pixel, as the ratio itself is usually not exact, e.g. 16 / 9.
+        let height_at_max_width = (max_width as f64 / ratio).round() as usize;
+        if height_at_max_width <= max_height {
+            (max_width, height_at_max_width)
+        } else {
+            (((max_height as f64 * ratio).round() as usize).min(max_width), max_height)
         }
     }
 
@@ -71,12 +178,30 @@ impl<'a> Chart<'a> {
         self
     }
 
+    /// Thin out the ticks of the axes that are added after this, so that their labels of about
+    /// `avg_label_px` pixels along the axis do not overlap, as counted by `Scale::fit_tick_count`.
+    pub fn set_tick_label_width(mut self, avg_label_px: usize) -> Self {
+        self.tick_label_width = Some(avg_label_px);
+        self
+    }
+
+    /// Return the width of the tick labels by which the ticks of new axes are thinned out, if set.
+    pub fn get_tick_label_width(&self) -> Option<usize> {
+        self.tick_label_width
+    }
+
     /// Add chart title.
     pub fn add_title(mut self, title: String) -> Self {
         self.title = title;
         self
     }
 
+    /// Set the description of the chart for screen readers, which defaults to the title.
+    pub fn set_description(mut self, description: &str) -> Self {
+        self.description = String::from(description);
+        self
+    }
+
     /// Set the margins of the chart to provided values.
     pub fn set_margins(mut self, top: isize, right: isize, bottom: isize, left: isize) -> Self {
         self.margin_top = top;
@@ -92,30 +217,206 @@ impl<'a> Chart<'a> {
         self
     }
 
+    /// Add the categorised values as bars, of which the chart creates the view and the axes itself.
+    /// The segments of each category are grouped side by side, along a value axis to the left.
+    /// This replaces the bottom and the left axis, so set the size and the margins first.
+    pub fn add_categorised_values<CAT, SEG, VAL>(mut self, values: &CategorisedValues<CAT, SEG, VAL>) -> Self
+    where
+        CAT: Clone + Default + Display + Hash + Eq,
+        SEG: Clone + Default + Display + Hash + Eq,
+        VAL: AddAssign<VAL> + Copy + Default + Display + ToF64,
+    {
+        // Collect the values from the transposed collection, so that each
+        // segment becomes a series of bars in its defined order.
+        let series = values.transposed();
+        let mut data = Vec::new();
+        for (segment, categories) in series.categories().map(series.category_index_to_label()) {
+            for (category, value) in categories.values().map(series.segment_index_to_label()) {
+                data.push((category.to_string(), value.to_f64() as f32, segment.to_string()));
+            }
+        }
+
+        self.categorised_bars = Some(CategorisedBars {
+            categories: values.category_keys().map(|category| category.to_string()).collect(),
+            keys: series.category_keys().map(|segment| segment.to_string()).collect(),
+            data,
+        });
+        self.add_categorised_axes();
+        self
+    }
+
+    /// Set whether the categorised values are flipped: the categories run down the left axis and
+    /// the segments of each category are grouped along a horizontal value axis at the bottom.
+    pub fn set_transposed(mut self, transposed: bool) -> Self {
+        self.transposed = transposed;
+        self.add_categorised_axes();
+        self
+    }
+
+    /// Create the scales of the categorised values, the band scale of the categories and the
+    /// linear scale of the values, along the dimensions of the view that suit the transposition.
+    fn categorised_scales(&self) -> Option<(ScaleBand, ScaleLinear)> {
+        let bars = self.categorised_bars.as_ref()?;
+
+        // Grouped bars start at zero and reach the value of a single segment.
+        let (min, max) = bars.data.iter().fold((0_f32, 0_f32), |(min, max), (_, value, _)| (min.min(*value), max.max(*value)));
+        let (min, max) = if min == max {
+            (min, max)
+        } else {
+            let step = nice_step(min, max, 10);
+            ((min / step).floor() * step, (max / step).ceil() * step)
+        };
+
+        let (category_range, value_range) = if self.transposed {
+            (vec![0, self.get_view_height()], vec![0, self.get_view_width()])
+        } else {
+            (vec![0, self.get_view_width()], vec![self.get_view_height(), 0])
+        };
+
+        Some((
+            ScaleBand::new().set_domain(bars.categories.clone()).set_range(category_range),
+            ScaleLinear::new().set_domain(vec![min, max]).set_range(value_range),
+        ))
+    }
+
+    /// Replace the bottom and the left axis by those of the categorised values, if any.
+    fn add_categorised_axes(&mut self) {
+        if let Some((categories, values)) = self.categorised_scales() {
+            let (bottom_axis, left_axis) = if self.transposed {
+                (Axis::new_bottom_axis(&values, self), Axis::new_left_axis(&categories, self))
+            } else {
+                (Axis::new_bottom_axis(&categories, self), Axis::new_left_axis(&values, self))
+            };
+
+            self.x_axis_bottom = Some(bottom_axis);
+            self.y_axis_left = Some(left_axis);
+        }
+    }
+
+    /// Lay out the categorised values, if any, in a view of grouped bars and pass it to `render`.
+    fn with_categorised_view<R>(&self, render: impl FnOnce(&dyn View) -> R) -> Result<Option<R>, ChartError> {
+        let (bars, (categories, values)) = match (&self.categorised_bars, self.categorised_scales()) {
+            (Some(bars), Some(scales)) => (bars, scales),
+            _ => return Ok(None),
+        };
+
+        if self.transposed {
+            let view = HorizontalBarView::new()
+                .set_x_scale(&values)
+                .set_y_scale(&categories)
+                .set_keys(bars.keys.clone())
+                .set_layout(BarLayout::Grouped)
+                .load_data(&bars.data)?;
+
+            Ok(Some(render(&view)))
+        } else {
+            let view = VerticalBarView::new()
+                .set_x_scale(&categories)
+                .set_y_scale(&values)
+                .set_keys(bars.keys.clone())
+                .set_layout(BarLayout::Grouped)
+                .load_data(&bars.data)?;
+
+            Ok(Some(render(&view)))
+        }
+    }
+
     /// Add an axis at the bottom of the chart.
     pub fn add_axis_bottom<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
         self.x_axis_bottom = Some(Axis::new_bottom_axis(scale, &self));
+        self.set_axis_scale(AxisPosition::Bottom, scale);
         self
     }
 
     /// Add an axis at the left of the chart.
     pub fn add_axis_left<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
         self.y_axis_left = Some(Axis::new_left_axis(scale, &self));
+        self.set_axis_scale(AxisPosition::Left, scale);
         self
     }
 
     /// Add an axis at the top of the chart.
     pub fn add_axis_top<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
         self.x_axis_top = Some(Axis::new_top_axis(scale, &self));
+        self.set_axis_scale(AxisPosition::Top, scale);
         self
     }
 
     /// Add an axis at the right of the chart.
     pub fn add_axis_right<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
         self.y_axis_right = Some(Axis::new_right_axis(scale, &self));
+        self.set_axis_scale(AxisPosition::Right, scale);
+        self
+    }
+
+    /// Keep the scale of the axis at the position, replacing the one of a previous axis.
+    fn set_axis_scale<T: ToString>(&mut self, position: AxisPosition, scale: &'a dyn Scale<T>) {
+        self.axis_scales.retain(|(axis_position, _)| *axis_position != position);
+        self.axis_scales.push((position, AnnotationScale::new(scale)));
+    }
+
+    /// Set the length of the line between the marker and the text of the annotations
+    /// that are added after this.
+    pub fn set_annotation_leader_line_length(mut self, length: f32) -> Self {
+        self.annotation_leader_line_length = Some(length);
+        self
+    }
+
+    /// Add an annotation, e.g. "record high", at the middle of the band of the category and at the
+    /// position of the value. These are resolved when rendering, by the scales of the categorised
+    /// values, or else by the band scale of an axis and the numeric scale of an axis across it.
+    pub fn add_annotation(mut self, category: &str, value: f32, text: &str) -> Self {
+        self.annotations.push((category.to_string(), value, text.to_string(), self.annotation_leader_line_length));
         self
     }
 
+    /// Place the annotations in a view, by the scales of the categorised values or of the axes.
+    fn annotation_view(&self) -> Result<AnnotationView<'_>, ChartError> {
+        let categorised_scales = self.categorised_scales();
+        let categorised_axes = match &categorised_scales {
+            Some((categories, values)) => {
+                let categories = AnnotationScale::new(categories as &dyn Scale<String>);
+                let values = AnnotationScale::new(values as &dyn Scale<f32>);
+                if self.transposed {
+                    vec![(AxisPosition::Bottom, values), (AxisPosition::Left, categories)]
+                } else {
+                    vec![(AxisPosition::Bottom, categories), (AxisPosition::Left, values)]
+                }
+            }
+            None => Vec::new(),
+        };
+        let axes = if categorised_scales.is_some() { &categorised_axes } else { &self.axis_scales };
+
+        let find_position = |positions: [AxisPosition; 2], position_of: &dyn Fn(&AnnotationScale) -> Option<f32>| {
+            positions.iter().find_map(|position| {
+                axes.iter()
+                    .filter(|(axis_position, _)| axis_position == position)
+                    .find_map(|(_, scale)| position_of(scale))
+            })
+        };
+        let horizontal = [AxisPosition::Bottom, AxisPosition::Top];
+        let vertical = [AxisPosition::Left, AxisPosition::Right];
+        let missing_value_axis = || ChartError::MissingDimension(String::from("value axis across the categories of the annotations"));
+
+        let mut view = AnnotationView::new();
+        for (category, value, text, leader_line_length) in self.annotations.iter() {
+            let category_position = |scale: &AnnotationScale| scale.category_position(category);
+            let value_position = |scale: &AnnotationScale| scale.value_position(*value);
+
+            let (x, y) = if let Some(x) = find_position(horizontal, &category_position) {
+                (x, find_position(vertical, &value_position).ok_or_else(missing_value_axis)?)
+            } else if let Some(y) = find_position(vertical, &category_position) {
+                (find_position(horizontal, &value_position).ok_or_else(missing_value_axis)?, y)
+            } else {
+                return Err(ChartError::InvalidScale(format!("the category {} is not in the domain of a band axis", category)));
+            };
+
+            view = view.add_annotation_at(x, y, text, *leader_line_length);
+        }
+
+        Ok(view)
+    }
+
     /// Add a label for the right of the chart.
     pub fn add_right_axis_label<T: ToString>(mut self, label: T) -> Self {
         if let Some(ref mut axis) = self.y_axis_right {
@@ -181,6 +482,38 @@ impl<'a> Chart<'a> {
         self.width - self.margin_left - self.margin_right
     }
 
+    /// Return the type of the scale of the X axis, at the bottom or else at the top of the chart,
+    /// or `None` when the chart has no X axis, e.g. before `add_axis_bottom` or `add_axis_top`.
+    pub fn x_scale_type(&self) -> Option<ScaleType> {
+        self.x_axis_bottom.as_ref().or(self.x_axis_top.as_ref()).map(Axis::get_scale_type)
+    }
+
+    /// Return the type of the scale of the Y axis, to the left or else to the right of the chart,
+    /// or `None` when the chart has no Y axis, e.g. before `add_axis_left` or `add_axis_right`.
+    pub fn y_scale_type(&self) -> Option<ScaleType> {
+        self.y_axis_left.as_ref().or(self.y_axis_right.as_ref()).map(Axis::get_scale_type)
+    }
+
+    /// Return the domain of the numeric scale of the values, from the first axis that represents one
+    /// in the order left, right, bottom and top, e.g. to caption the chart with the bounds it uses.
+    pub fn value_domain(&self) -> Option<(f32, f32)> {
+        [&self.y_axis_left, &self.y_axis_right, &self.x_axis_bottom, &self.x_axis_top]
+            .iter()
+            .find_map(|axis| axis.as_ref().and_then(Axis::get_domain_limits))
+    }
+
+    /// Return the categories of the band scale, from the first axis that represents one
+    /// in the order bottom, top, left and right.
+    pub fn category_domain(&self) -> Vec<&String> {
+        [&self.x_axis_bottom, &self.x_axis_top, &self.y_axis_left, &self.y_axis_right]
+            .iter()
+            .filter_map(|axis| axis.as_ref())
+            .map(Axis::get_category_domain)
+            .find(|categories| !categories.is_empty())
+            .map(|categories| categories.iter().collect())
+            .unwrap_or_default()
+    }
+
     /// Return the width of the chart.
     pub fn get_chart_width(&self) -> isize {
         self.width
@@ -191,6 +524,93 @@ impl<'a> Chart<'a> {
         self.height
     }
 
+    /// Return the total height the chart needs when rendered, made of the height of the view
+    /// plus the space taken by the title, the horizontal axes and a legend above or below the view.
+    pub fn total_height(&self) -> usize {
+        let mut height = self.get_view_height() + self.title_height();
+
+        if self.x_axis_top.is_some() {
+            height += Self::horizontal_axis_height(&self.x_axis_top);
+        }
+
+        match self.legend_position {
+            Some(AxisPosition::Top) => {
+                height += self.legend().height() as isize;
+                if self.title.is_empty() {
+                    height += 10;
+                }
+                if self.x_axis_bottom.is_some() {
+                    height += Self::horizontal_axis_height(&self.x_axis_bottom);
+                }
+            },
+            Some(AxisPosition::Bottom) => {
+                height += Self::horizontal_axis_height(&self.x_axis_bottom) + self.legend().height() as isize;
+            },
+            _ if self.x_axis_bottom.is_some() => height += Self::horizontal_axis_height(&self.x_axis_bottom),
+            _ => {},
+        }
+
+        height.max(0) as usize
+    }
+
+    /// Return the height taken by the chart title.
+    fn title_height(&self) -> isize {
+        if !self.title.is_empty() {
+            45
+        } else {
+            0
+        }
+    }
+
+    /// Return the height taken by a horizontal axis, including its tick labels and axis label.
+    /// These sizes are hardcoded and work with current hardcoded axis fonts.
+    fn horizontal_axis_height(axis: &Option<Axis>) -> isize {
+        match axis {
+            Some(axis) if axis.has_label() => 52,
+            Some(_) => 36,
+            None => 16,
+        }
+    }
+
+    /// Return the width available to the legend at the current legend position.
+    fn legend_width(&self) -> isize {
+        match self.legend_position {
+            Some(AxisPosition::Left) => {
+                let axis_width = Self::vertical_axis_width(&self.y_axis_left);
+                self.margin_left - axis_width - 10 // 10 is the padding from the left of the chart
+            },
+            Some(AxisPosition::Right) => self.margin_right - Self::vertical_axis_width(&self.y_axis_right),
+            _ => self.width - self.margin_right - self.margin_left,
+        }
+    }
+
+    /// Return the width taken by a vertical axis, including its tick labels and axis label.
+    fn vertical_axis_width(axis: &Option<Axis>) -> isize {
+        match axis {
+            Some(axis) if axis.has_label() => 68,
+            Some(_) => 50,
+            None => 20,
+        }
+    }
+
+    /// Create the legend of the chart from the legend entries of all views.
+    fn legend(&self) -> Legend {
+        let mut legend_entries = self
+            .views
+            .iter()
+            .map(|view| view.get_legend_entries())
+            .flatten()
+            .collect::<Vec<LegendEntry>>();
+
+        if let Ok(Some(entries)) = self.with_categorised_view(|view| view.get_legend_entries()) {
+            legend_entries.extend(entries);
+        }
+
+        Legend::new(legend_entries, self.legend_width().max(0) as usize)
+            .set_swatch_borders(self.legend_swatch_borders)
+            .set_class(self.legend_class.clone())
+    }
+
     /// Return the height of the view.
     pub fn get_view_height(&self) -> isize {
         self.height - self.margin_top - self.margin_bottom
@@ -202,6 +622,12 @@ impl<'a> Chart<'a> {
         self
     }
 
+    /// Set whether legend swatches with a color close to the background should get a thin border.
+    pub fn set_legend_swatch_borders(mut self, swatch_borders: bool) -> Self {
+        self.legend_swatch_borders = swatch_borders;
+        self
+    }
+
     /// Set the rotation in degrees of the bottom axis tick labels.
     pub fn set_bottom_axis_tick_label_rotation(mut self, rotation: isize) -> Self {
         match &mut self.x_axis_bottom {
@@ -274,9 +700,156 @@ impl<'a> Chart<'a> {
         self
     }
 
+    /// Set the text to display before and after the labels on the left axis, e.g. a currency or a unit.
+    pub fn set_left_axis_tick_label_affixes(mut self, prefix: &str, suffix: &str) -> Self {
+        if let Some(ref mut axis) = self.y_axis_left {
+            axis.set_tick_label_prefix(prefix);
+            axis.set_tick_label_suffix(suffix);
+        }
+        self
+    }
+
+    /// Set the text to display before and after the labels on the right axis, e.g. a currency or a unit.
+    pub fn set_right_axis_tick_label_affixes(mut self, prefix: &str, suffix: &str) -> Self {
+        if let Some(ref mut axis) = self.y_axis_right {
+            axis.set_tick_label_prefix(prefix);
+            axis.set_tick_label_suffix(suffix);
+        }
+        self
+    }
+
+    /// Set the text to display before and after the labels on the top axis, e.g. a currency or a unit.
+    pub fn set_top_axis_tick_label_affixes(mut self, prefix: &str, suffix: &str) -> Self {
+        if let Some(ref mut axis) = self.x_axis_top {
+            axis.set_tick_label_prefix(prefix);
+            axis.set_tick_label_suffix(suffix);
+        }
+        self
+    }
+
+    /// Set the text to display before and after the labels on the bottom axis, e.g. a currency or a unit.
+    pub fn set_bottom_axis_tick_label_affixes(mut self, prefix: &str, suffix: &str) -> Self {
+        if let Some(ref mut axis) = self.x_axis_bottom {
+            axis.set_tick_label_prefix(prefix);
+            axis.set_tick_label_suffix(suffix);
+        }
+        self
+    }
+
+    /// Set whether the bottom axis line should span only the bands of its scale.
+    pub fn set_bottom_axis_domain_line_trim(mut self, trim: bool) -> Self {
+        if let Some(ref mut axis) = self.x_axis_bottom {
+            axis.set_domain_line_trim(trim);
+        }
+        self
+    }
+
+    /// Set whether the top axis line should span only the bands of its scale.
+    pub fn set_top_axis_domain_line_trim(mut self, trim: bool) -> Self {
+        if let Some(ref mut axis) = self.x_axis_top {
+            axis.set_domain_line_trim(trim);
+        }
+        self
+    }
+
+    /// Set whether the left axis line should span only the bands of its scale.
+    pub fn set_left_axis_domain_line_trim(mut self, trim: bool) -> Self {
+        if let Some(ref mut axis) = self.y_axis_left {
+            axis.set_domain_line_trim(trim);
+        }
+        self
+    }
+
+    /// Set whether gridlines are drawn at the ticks of the left axis and, dashed, halfway between them.
+    pub fn set_left_axis_gridlines(mut self, major: bool, minor: bool) -> Self {
+        if let Some(ref mut axis) = self.y_axis_left {
+            axis.set_gridlines(major);
+            axis.set_minor_gridlines(minor);
+        }
+        self
+    }
+
+    /// Set whether gridlines are drawn at the ticks of the right axis and, dashed, halfway between them.
+    pub fn set_right_axis_gridlines(mut self, major: bool, minor: bool) -> Self {
+        if let Some(ref mut axis) = self.y_axis_right {
+            axis.set_gridlines(major);
+            axis.set_minor_gridlines(minor);
+        }
+        self
+    }
+
+    /// Set whether gridlines are drawn at the ticks of the top axis and, dashed, halfway between them.
+    pub fn set_top_axis_gridlines(mut self, major: bool, minor: bool) -> Self {
+        if let Some(ref mut axis) = self.x_axis_top {
+            axis.set_gridlines(major);
+            axis.set_minor_gridlines(minor);
+        }
+        self
+    }
+
+    /// Set whether gridlines are drawn at the ticks of the bottom axis and, dashed, halfway between them.
+    pub fn set_bottom_axis_gridlines(mut self, major: bool, minor: bool) -> Self {
+        if let Some(ref mut axis) = self.x_axis_bottom {
+            axis.set_gridlines(major);
+            axis.set_minor_gridlines(minor);
+        }
+        self
+    }
+
+    /// Set a class to add to the group of the legend, e.g. to style it with an external stylesheet.
+    pub fn set_legend_class(mut self, class: &str) -> Self {
+        self.legend_class = Some(class.to_owned());
+        self
+    }
+
+    /// Set a class to add to the group of the left axis.
+    pub fn set_left_axis_class(mut self, class: &str) -> Self {
+        if let Some(ref mut axis) = self.y_axis_left {
+            axis.set_class(class);
+        }
+        self
+    }
+
+    /// Set a class to add to the group of the right axis.
+    pub fn set_right_axis_class(mut self, class: &str) -> Self {
+        if let Some(ref mut axis) = self.y_axis_right {
+            axis.set_class(class);
+        }
+        self
+    }
+
+    /// Set a class to add to the group of the top axis.
+    pub fn set_top_axis_class(mut self, class: &str) -> Self {
+        if let Some(ref mut axis) = self.x_axis_top {
+            axis.set_class(class);
+        }
+        self
+    }
+
+    /// Set a class to add to the group of the bottom axis.
+    pub fn set_bottom_axis_class(mut self, class: &str) -> Self {
+        if let Some(ref mut axis) = self.x_axis_bottom {
+            axis.set_class(class);
+        }
+        self
+    }
+
+    /// Set whether the right axis line should span only the bands of its scale.
+    pub fn set_right_axis_domain_line_trim(mut self, trim: bool) -> Self {
+        if let Some(ref mut axis) = self.y_axis_right {
+            axis.set_domain_line_trim(trim);
+        }
+        self
+    }
+
     /// Generate the SVG for the chart and its components.
-    fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new().set("class", "g-chart");
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        let _ids = IdScope::enter();
+        let description = if self.description.is_empty() { &self.title } else { &self.description };
+        let mut group = Group::new()
+            .set("class", "g-chart")
+            .set("role", "img")
+            .set("aria-label", description.as_str());
 
         // Add chart title
         if self.title.len() > 0 {
@@ -346,92 +919,31 @@ impl<'a> Chart<'a> {
             format!("translate({},{})", self.margin_left, self.margin_top),
         );
 
+        if let Some(view_svg) = self.with_categorised_view(|view| view.to_svg())? {
+            view_group.append(view_svg?);
+        }
         for view in self.views.iter() {
             view_group.append(view.to_svg()?);
         }
+        if !self.annotations.is_empty() {
+            view_group.append(self.annotation_view()?.to_svg()?);
+        }
         group.append(view_group);
 
         if let Some(legend_position) = self.legend_position {
-            let width;
-            let x_offset;
-            let y_offset;
-
-            match legend_position {
+            let (x_offset, y_offset) = match legend_position {
                 AxisPosition::Top => {
-                    let axis_height = {
-                        if self.title.len() > 0 {
-                            45
-                        } else {
-                            10
-                        }
-                    };
-                    width = self.width - self.margin_right - self.margin_left;
-                    x_offset = self.margin_left;
-                    y_offset = axis_height;
-                }
-                AxisPosition::Bottom => {
-                    // Compute the height of the bottom axis that should serve
-                    // as an offset for the legend.
-                    // These sizes are hardcoded and work with current hardcoded axis fonts.
-                    // When a necessity will arise to have custom axis font sizes,
-                    // these will have to be dynamically computed.
-                    let axis_height = {
-                        if let Some(ref axis) = self.x_axis_bottom {
-                            if axis.has_label() {
-                                52
-                            } else {
-                                36
-                            }
-                        } else {
-                            16
-                        }
-                    };
-                    width = self.width - self.margin_right - self.margin_left;
-                    x_offset = self.margin_left;
-                    y_offset = self.height - self.margin_bottom + axis_height;
-                }
-                AxisPosition::Left => {
-                    let axis_width = {
-                        if let Some(ref axis) = self.y_axis_left {
-                            if axis.has_label() {
-                                68
-                            } else {
-                                50
-                            }
-                        } else {
-                            20
-                        }
-                    };
-                    width = self.margin_left - axis_width - 10; // 10 is described in the comment below
-                    x_offset = 10; // always have a 10px padding from the left of the chart
-                    y_offset = self.margin_top;
-                }
-                AxisPosition::Right => {
-                    let axis_width = {
-                        if let Some(ref axis) = self.y_axis_right {
-                            if axis.has_label() {
-                                68
-                            } else {
-                                50
-                            }
-                        } else {
-                            20
-                        }
-                    };
-                    width = self.margin_right - axis_width;
-                    x_offset = self.width - self.margin_right + axis_width;
-                    y_offset = self.margin_top;
+                    let title_height = if self.title.is_empty() { 10 } else { self.title_height() };
+                    (self.margin_left, title_height)
                 }
+                // Use the height of the bottom axis as an offset for the legend.
+                AxisPosition::Bottom => (self.margin_left, self.height - self.margin_bottom + Self::horizontal_axis_height(&self.x_axis_bottom)),
+                // Always have a 10px padding from the left of the chart.
+                AxisPosition::Left => (10, self.margin_top),
+                AxisPosition::Right => (self.width - self.margin_right + Self::vertical_axis_width(&self.y_axis_right), self.margin_top),
             };
 
-            let legend_entries = self
-                .views
-                .iter()
-                .map(|view| view.get_legend_entries())
-                .flatten()
-                .collect::<Vec<LegendEntry>>();
-            let legend = Legend::new(legend_entries, width as usize);
-            let mut legend_group = legend.to_svg()?;
+            let mut legend_group = self.legend().to_svg()?;
             legend_group.assign("transform", format!("translate({},{})", x_offset, y_offset));
 
             group.append(legend_group);
@@ -478,3 +990,375 @@ impl<'a> Chart<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::test_support::*;
+
+    #[test]
+    fn total_height_includes_title_and_axis() {
+        let x = band_scale(&["A", "B"], vec![0, 700]);
+        let y = linear_scale(0_f32, 100_f32, vec![460, 0]);
+
+        let untitled = Chart::new()
+            .add_axis_bottom(&x)
+            .add_axis_left(&y)
+            .add_bottom_axis_label("Category");
+        let titled = Chart::new()
+            .add_title(String::from("Title"))
+            .add_axis_bottom(&x)
+            .add_axis_left(&y)
+            .add_bottom_axis_label("Category");
+
+        let view_height = untitled.get_view_height() as usize;
+        assert_eq!(untitled.total_height(), view_height + 52);
+        assert_eq!(titled.total_height(), untitled.total_height() + 45);
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&vec![("A", 10, "Cassette"), ("B", 20, "Vinyl")])
+            .unwrap();
+        let titled = titled.add_view(&view);
+        let legend_height = titled.legend().height();
+        assert!(legend_height > 0);
+
+        let legend_top = titled.add_legend_at(AxisPosition::Top);
+        assert_eq!(legend_top.total_height(), view_height + 45 + legend_height + 52);
+
+        let legend_bottom = legend_top.add_legend_at(AxisPosition::Bottom);
+        assert_eq!(legend_bottom.total_height(), view_height + 45 + 52 + legend_height);
+    }
+
+    #[test]
+    fn suggested_size_for_few_categories() {
+        assert_eq!(Chart::suggested_size(3, false), (500, 400));
+        assert_eq!(Chart::suggested_size(0, false), Chart::suggested_size(3, false));
+        assert_eq!(Chart::suggested_size(3, true), (650, 400));
+    }
+
+    #[test]
+    fn fit_aspect_limited_by_width() {
+        let (width, height) = Chart::fit_aspect(800, 600, 16_f32 / 9_f32);
+        assert_eq!((width, height), (800, 450));
+        assert_eq!(width * 9, height * 16);
+    }
+
+    #[test]
+    fn fit_aspect_limited_by_height() {
+        assert_eq!(Chart::fit_aspect(800, 300, 16_f32 / 9_f32), (533, 300));
+        assert_eq!(Chart::fit_aspect(800, 600, 1_f32), (600, 600));
+        assert_eq!(Chart::fit_aspect(800, 600, 0_f32), (800, 600));
+    }
+
+    #[test]
+    fn suggested_size_for_many_categories() {
+        let (width, height) = Chart::suggested_size(20, true);
+        assert_eq!(width, 100 + 20 * SUGGESTED_CATEGORY_WIDTH + SUGGESTED_LEGEND_WIDTH);
+        assert_eq!(height, 600);
+
+        let (wider, _) = Chart::suggested_size(40, true);
+        assert_eq!(wider - width, 20 * SUGGESTED_CATEGORY_WIDTH);
+    }
+
+    #[test]
+    fn reports_the_scale_types_of_the_axes() {
+        let categories = band_scale(&["A", "B"], vec![0, 700]);
+        let values = linear_scale(0_f32, 100_f32, vec![460, 0]);
+        let bar_chart = Chart::new()
+            .add_axis_bottom(&categories)
+            .add_axis_left(&values);
+
+        assert_eq!(bar_chart.x_scale_type(), Some(ScaleType::Band));
+        assert_eq!(bar_chart.y_scale_type(), Some(ScaleType::Linear));
+
+        let horizontal = linear_scale(0_f32, 10_f32, vec![0, 700]);
+        let scatter_chart = Chart::new()
+            .add_axis_top(&horizontal)
+            .add_axis_right(&values);
+
+        assert_eq!(scatter_chart.x_scale_type(), Some(ScaleType::Linear));
+        assert_eq!(scatter_chart.y_scale_type(), Some(ScaleType::Linear));
+        assert_eq!(Chart::new().x_scale_type(), None);
+        assert_eq!(Chart::new().add_axis_bottom(&categories).y_scale_type(), None);
+    }
+
+    #[test]
+    fn domains_of_the_rendered_axes() {
+        let sales = CategorisedValues::new().add_data(vec![("1977", 36), ("1978", 97), ("1979", 61)]);
+        let (min, max) = sales.value_domain();
+        let categories = sales.category_domain().into_iter().cloned().collect::<Vec<&str>>();
+
+        let chart = Chart::new().set_margins(90, 40, 50, 60);
+        let x = ScaleBand::new()
+            .set_domain(categories.iter().map(|category| category.to_string()).collect())
+            .set_range(vec![0, chart.get_view_width()]);
+        let y = linear_scale(min, max, vec![chart.get_view_height(), 0]);
+        let chart = chart.add_axis_bottom(&x).add_axis_left(&y);
+
+        assert_eq!(chart.value_domain(), Some((0_f32, 100_f32)));
+        assert_eq!(chart.category_domain(), vec!["1977", "1978", "1979"]);
+
+        // The top of the rendered value axis is the end of the reported domain.
+        let svg = chart.to_svg().unwrap().to_string();
+        let y_axis = svg.split("class=\"y-axis\"").nth(1).unwrap();
+        assert!(y_axis.contains("<g class=\"tick\" transform=\"translate(0,0)\">"));
+        assert!(y_axis.contains(">\n100\n</text>"));
+
+        assert_eq!(Chart::new().value_domain(), None);
+        assert!(Chart::new().category_domain().is_empty());
+    }
+
+    #[test]
+    fn chart_is_labelled_for_screen_readers() {
+        let titled = Chart::new().add_title(String::from("Sales")).to_svg().unwrap().to_string();
+        assert!(titled.starts_with("<g aria-label=\"Sales\" class=\"g-chart\" role=\"img\">"));
+
+        let described = Chart::new()
+            .add_title(String::from("Sales"))
+            .set_description("Sales per format, 1977 to 2010")
+            .to_svg()
+            .unwrap()
+            .to_string();
+        assert!(described.starts_with("<g aria-label=\"Sales per format, 1977 to 2010\""));
+    }
+
+    #[test]
+    fn panels_render_with_the_shared_value_axis() {
+        let data = [
+            vec![
+                ("Jan", "North", 12),
+                ("Jan", "South", 25),
+                ("Feb", "North", 31),
+            ],
+            vec![("Jan", "North", 97), ("Feb", "South", 148)],
+            vec![("Jan", "North", 56), ("Feb", "North", 8)],
+        ];
+        let panels = data
+            .iter()
+            .map(|panel| CategorisedValues::new().add_data(panel.clone()))
+            .collect::<Vec<_>>();
+        let panel_refs = panels.iter().collect::<Vec<_>>();
+
+        let x = band_scale(&["Jan", "Feb"], vec![0, 200]);
+        let y = CategorisedValues::shared_value_scale(&panel_refs, vec![300, 0]);
+
+        let y_axes = data
+            .iter()
+            .map(|panel| {
+                let bars = panel
+                    .iter()
+                    .map(|(category, segment, value)| (*category, *value, *segment))
+                    .collect::<Vec<_>>();
+                let view = VerticalBarView::new()
+                    .set_x_scale(&x)
+                    .set_y_scale(&y)
+                    .load_data(&bars)
+                    .unwrap();
+                let svg = Chart::new()
+                    .set_width(300)
+                    .set_height(440)
+                    .add_view(&view)
+                    .add_axis_bottom(&x)
+                    .add_axis_left(&y)
+                    .to_svg()
+                    .unwrap()
+                    .to_string();
+
+                svg.split("class=\"y-axis\"")
+                    .nth(1)
+                    .unwrap()
+                    .split("class=\"g-view\"")
+                    .next()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        // Every panel has the global maximum at the top of its value axis, not its own maximum.
+        for y_axis in y_axes.iter() {
+            assert!(y_axis.contains("<g class=\"tick\" transform=\"translate(0,0)\">"));
+            assert!(y_axis.contains(">\n160\n</text>"));
+            assert_eq!(y_axis, &y_axes[0]);
+        }
+    }
+
+    #[test]
+    fn major_and_minor_gridline_styles() {
+        let chart = Chart::new().set_width(700).set_height(500).set_margins(50, 50, 50, 50);
+        let y = linear_scale(0_f32, 100_f32, vec![chart.get_view_height(), 0]);
+        let svg = chart
+            .add_axis_left(&y)
+            .set_left_axis_gridlines(true, true)
+            .to_svg()
+            .unwrap()
+            .to_string();
+
+        let majors = svg.lines().filter(|line| line.contains("gridline-major")).collect::<Vec<&str>>();
+        let minors = svg.lines().filter(|line| line.contains("gridline-minor")).collect::<Vec<&str>>();
+        let ticks = svg.matches("class=\"tick\"").count();
+        assert_eq!(majors.len(), ticks);
+        assert_eq!(minors.len(), ticks - 1);
+        assert!(majors.iter().all(|line| !line.contains("stroke-dasharray")));
+        assert!(minors.iter().all(|line| line.contains("stroke-dasharray=\"2,2\"")));
+
+        // The gridlines span the view at the rendered ticks, the top one at the end of the domain.
+        assert!(svg.contains("<g class=\"tick\" transform=\"translate(0,0)\">"));
+        assert!(majors.iter().any(|line| line.contains("x2=\"600\"") && line.contains("y1=\"0\" y2=\"0\"")));
+
+        let without_gridlines = Chart::new().add_axis_left(&y).to_svg().unwrap().to_string();
+        assert!(!without_gridlines.contains("gridline"));
+    }
+
+    #[test]
+    fn transposed_bars_run_down_the_y_axis() {
+        let sales = CategorisedValues::new().add_data(vec![
+            ("1977", "8 - Track", 127),
+            ("1977", "Cassette", 37),
+            ("1978", "8 - Track", 134),
+            ("1978", "Cassette", 61),
+            ("1979", "8 - Track", 102),
+            ("1979", "Cassette", 79),
+        ]);
+
+        let chart = Chart::new()
+            .set_width(800)
+            .set_height(600)
+            .set_margins(50, 50, 50, 100)
+            .add_categorised_values(&sales);
+
+        // Without transposition the categories are along the bottom axis, the values to the left.
+        assert_eq!(chart.x_scale_type(), Some(ScaleType::Band));
+        assert_eq!(chart.y_scale_type(), Some(ScaleType::Linear));
+
+        // The chart moves the categories onto the band scale of the left axis and the values along the bottom axis.
+        let chart = chart.set_transposed(true);
+        assert_eq!(chart.y_scale_type(), Some(ScaleType::Band));
+        assert_eq!(chart.x_scale_type(), Some(ScaleType::Linear));
+        assert_eq!(chart.y_axis_left.as_ref().unwrap().get_category_domain(), &vec!["1977", "1978", "1979"]);
+        assert_eq!(chart.x_axis_bottom.as_ref().unwrap().get_domain_limits(), Some((0_f32, 140_f32)));
+
+        let categories = band_scale(&["1977", "1978", "1979"], vec![0, chart.get_view_height()]);
+        let values = linear_scale(0_f32, 140_f32, vec![0, chart.get_view_width()]);
+
+        let svg = chart.to_svg().unwrap().to_string();
+        let view_svg = svg.split("class=\"g-view\"").nth(1).unwrap();
+        assert!(view_svg.contains("class=\"horizontal-bar-view\""));
+        let bars = bar_blocks(view_svg);
+
+        // Each category has a bar per segment, placed one below the other within its band,
+        // and the bars grow horizontally by their value.
+        assert_eq!(bars.len(), 6);
+        for (category, pair) in ["1977", "1978", "1979"].iter().zip(bars.chunks(2)) {
+            let band_start = categories.scale(&category.to_string());
+            assert_eq!(pair[0].0, (0_f32, band_start));
+            assert!((pair[0].0.1 + pair[0].2 - pair[1].0.1).abs() < 1e-3);
+            assert!((pair[0].2 + pair[1].2 - categories.bandwidth().unwrap()).abs() < 1e-3);
+        }
+        let widths = bars.iter().map(|bar| bar.1).collect::<Vec<f32>>();
+        let expected = [127, 37, 134, 61, 102, 79].iter().map(|value| values.scale(&(*value as f32))).collect::<Vec<f32>>();
+        assert_eq!(widths, expected);
+    }
+
+    #[test]
+    fn ids_are_unique_within_a_render_and_stable_across_renders() {
+        let x = band_scale(&["A", "B"], vec![0, 600]);
+        let y = linear_scale(0_f32, 100_f32, vec![400, 0]);
+        let data = vec![("A", 40_f32, "Cassette"), ("B", 60_f32, "Cassette")];
+        let first = VerticalBarView::new().set_x_scale(&x).set_y_scale(&y).set_shadow(true).load_data(&data).unwrap();
+        let second = VerticalBarView::new().set_x_scale(&x).set_y_scale(&y).set_shadow(true).load_data(&data).unwrap();
+        let chart = Chart::new().add_view(&first).add_view(&second);
+
+        let svg = chart.to_svg().unwrap().to_string();
+        assert!(svg.contains("<filter height=\"140%\" id=\"shadow-0\""));
+        assert!(svg.contains("<filter height=\"140%\" id=\"shadow-1\""));
+        assert_eq!(chart.to_svg().unwrap().to_string(), svg);
+
+        // A view rendered on its own numbers its ids from zero as well.
+        assert!(first.to_svg().unwrap().to_string().contains("id=\"shadow-0\""));
+    }
+
+    #[test]
+    fn classes_of_the_views_axes_and_legend() {
+        let x = band_scale(&["A", "B"], vec![0, 700]);
+        let y = linear_scale(0_f32, 100_f32, vec![460, 0]);
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&vec![("A", 40_f32, "x"), ("B", 60_f32, "y")])
+            .unwrap()
+            .set_class("sales");
+
+        let svg = Chart::new()
+            .add_view(&view)
+            .add_axis_bottom(&x)
+            .add_axis_left(&y)
+            .add_legend_at(AxisPosition::Top)
+            .set_bottom_axis_class("categories")
+            .set_left_axis_class("values")
+            .set_legend_class("keys")
+            .to_svg()
+            .unwrap()
+            .to_string();
+
+        assert!(svg.contains("class=\"vertical-bar-view sales\""));
+        assert!(svg.contains("class=\"x-axis categories\""));
+        assert!(svg.contains("class=\"y-axis values\""));
+        assert!(svg.contains("class=\"g-legend keys\""));
+    }
+
+    #[test]
+    fn annotation_at_category_and_value() {
+        let chart = Chart::new();
+        let x = band_scale(&["1977", "1978", "1979"], vec![0, chart.get_view_width()]);
+        let y = linear_scale(0_f32, 400_000_000_f32, vec![chart.get_view_height(), 0]);
+
+        let chart = chart
+            .add_axis_bottom(&x)
+            .add_axis_left(&y)
+            .set_annotation_leader_line_length(20_f32)
+            .add_annotation("1978", 200_000_000_f32, "Record high");
+        let svg = chart.to_svg().unwrap().to_string();
+        let view_svg = svg.split("class=\"g-view\"").nth(1).unwrap();
+        let center = x.scale(&String::from("1978")) + x.bandwidth().unwrap() / 2_f32;
+
+        assert!(view_svg.contains("class=\"annotation-view\""));
+        assert!(view_svg.contains(&format!("transform=\"translate({},{})\"", center, y.scale(&200_000_000_f32))));
+        assert!(view_svg.contains("class=\"annotation-marker\" cx=\"0\" cy=\"0\""));
+        assert!(view_svg.contains("class=\"annotation-leader\""));
+        assert!(view_svg.contains("y=\"-26\">\nRecord high\n</text>"));
+
+        let unknown = Chart::new().add_axis_bottom(&x).add_axis_left(&y).add_annotation("2000", 0_f32, "Missing");
+        assert!(matches!(unknown.to_svg().err(), Some(ChartError::InvalidScale(_))));
+        let without_values = Chart::new().add_axis_bottom(&x).add_annotation("1978", 0_f32, "Missing");
+        assert!(matches!(without_values.to_svg().err(), Some(ChartError::MissingDimension(_))));
+    }
+
+    #[test]
+    fn annotation_follows_the_transposed_categorised_values() {
+        let sales = CategorisedValues::new().add_data(vec![("1977", "Cassette", 37), ("1978", "Cassette", 61)]);
+
+        let chart = Chart::new()
+            .add_categorised_values(&sales)
+            .set_transposed(true)
+            .add_annotation("1978", 50_f32, "Cassettes take over");
+        let categories = band_scale(&["1977", "1978"], vec![0, chart.get_view_height()]);
+        let (min, max) = chart.value_domain().unwrap();
+        let values = linear_scale(min, max, vec![0, chart.get_view_width()]);
+
+        let svg = chart.to_svg().unwrap().to_string();
+        let center = categories.scale(&String::from("1978")) + categories.bandwidth().unwrap() / 2_f32;
+        assert!(svg.contains(&format!("transform=\"translate({},{})\"", values.scale(&50_f32), center)));
+    }
+
+    #[test]
+    fn categorised_counts_are_laid_out() {
+        let letters = CategorisedValues::new().add_data("hello world".chars().filter(|c| c.is_alphabetic()));
+        let chart = Chart::new().add_categorised_values(&letters);
+
+        assert_eq!(chart.value_domain(), Some((0_f32, 3_f32)));
+        assert_eq!(chart.category_domain(), vec!["h", "e", "l", "o", "w", "r", "d"]);
+        assert_eq!(bar_blocks(&chart.to_svg().unwrap().to_string()).len(), 7);
+    }
+}
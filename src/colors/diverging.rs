@@ -0,0 +1,95 @@
+use crate::colors::{interpolate_color, Color};
+
+/// A color scale that interpolates between a low, a middle and a high color, with
+/// the middle color pinned at a center value of the domain. Each side of the center
+/// is interpolated separately, so the center keeps its color even for an asymmetric domain.
+#[derive(Debug, Clone)]
+pub struct DivergingColorScale {
+    low: Color,
+    mid: Color,
+    high: Color,
+    start: f32,
+    center: f32,
+    end: f32,
+}
+
+impl DivergingColorScale {
+    /// Create a new diverging color scale over the domain -1..1, centered on 0.
+    pub fn new(low: Color, mid: Color, high: Color) -> Self {
+        Self {
+            low,
+            mid,
+            high,
+            start: -1_f32,
+            center: 0_f32,
+            end: 1_f32,
+        }
+    }
+
+    /// Set the domain of the scale, mapped to the low and high colors.
+    pub fn set_domain(mut self, start: f32, end: f32) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Set the domain value that is mapped to the middle color.
+    pub fn set_center(mut self, center: f32) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Get the color for the given domain value. Values outside of the domain are clamped.
+    pub fn scale(&self, value: f32) -> Color {
+        if (value - self.center) * (self.start - self.center) > 0_f32 {
+            Self::interpolate(&self.mid, &self.low, Self::fraction(value, self.center, self.start))
+        } else if (value - self.center) * (self.end - self.center) > 0_f32 {
+            Self::interpolate(&self.mid, &self.high, Self::fraction(value, self.center, self.end))
+        } else {
+            self.mid.clone()
+        }
+    }
+
+    /// Compute how far the value is from `from` towards `to`, clamped between 0 and 1.
+    fn fraction(value: f32, from: f32, to: f32) -> f32 {
+        ((value - from) / (to - from)).clamp(0_f32, 1_f32)
+    }
+
+    /// Interpolate each RGB channel between two colors.
+    fn interpolate(from: &Color, to: &Color, t: f32) -> Color {
+        Color::from_hex(&interpolate_color(&from.as_hex(), &to.as_hex(), t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anomaly_scale() -> DivergingColorScale {
+        DivergingColorScale::new(Color::from_hex("#0000ff"), Color::from_hex("#ffffff"), Color::from_hex("#ff0000"))
+            .set_domain(-5_f32, 5_f32)
+            .set_center(0_f32)
+    }
+
+    #[test]
+    fn center_maps_to_mid_color() {
+        assert_eq!(anomaly_scale().scale(0_f32).as_hex(), "#ffffff");
+    }
+
+    #[test]
+    fn extremes_map_to_low_and_high_colors() {
+        let scale = anomaly_scale();
+        assert_eq!(scale.scale(-5_f32).as_hex(), "#0000ff");
+        assert_eq!(scale.scale(5_f32).as_hex(), "#ff0000");
+        assert_eq!(scale.scale(-50_f32).as_hex(), "#0000ff");
+        assert_eq!(scale.scale(2.5_f32).as_hex(), "#ff8080");
+    }
+
+    #[test]
+    fn center_is_pinned_in_asymmetric_domain() {
+        let scale = anomaly_scale().set_domain(-1_f32, 9_f32);
+        assert_eq!(scale.scale(0_f32).as_hex(), "#ffffff");
+        assert_eq!(scale.scale(-0.5_f32).as_hex(), "#8080ff");
+        assert_eq!(scale.scale(4.5_f32).as_hex(), "#ff8080");
+    }
+}
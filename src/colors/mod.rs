@@ -1,5 +1,8 @@
+mod diverging;
+pub use diverging::DivergingColorScale;
+
 /// A struct that represents a color.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Color {
     hex: String,
 }
@@ -64,8 +67,103 @@ impl Color {
         )
     }
 
+    /// Create a color from a hex string.
+    pub fn from_hex(hex: &str) -> Self {
+        Color { hex: String::from(hex) }
+    }
+
     /// Represent a color as a hex string.
     pub fn as_hex(&self) -> String {
         String::from(&self.hex)
     }
 }
+
+/// Parse a color given as a `#rgb` or `#rrggbb` hex string into its red, green and blue channels.
+pub(crate) fn parse_rgb(hex: &str) -> Option<[u8; 3]> {
+    let digits = hex.trim().trim_start_matches('#');
+    let digits = match digits.len() {
+        3 => digits.chars().flat_map(|digit| vec![digit, digit]).collect::<String>(),
+        6 => digits.to_owned(),
+        _ => return None,
+    };
+
+    let mut channels = [0_u8; 3];
+    for (i, channel) in channels.iter_mut().enumerate() {
+        *channel = u8::from_str_radix(digits.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+
+    Some(channels)
+}
+
+/// Blend two colors given as `#rgb` or `#rrggbb` hex strings by linearly interpolating each
+/// RGB channel, where `t` is clamped between 0 (`from`) and 1 (`to`). Channels are rounded to the
+/// nearest value, so halfway between `#000000` and `#ffffff` is `#808080`. Falls back to the
+/// closest of the two colors if either can not be parsed.
+pub fn interpolate_color(from: &str, to: &str, t: f32) -> String {
+    let t = t.clamp(0_f32, 1_f32);
+
+    match (parse_rgb(from), parse_rgb(to)) {
+        (Some(from_rgb), Some(to_rgb)) => {
+            let mut channels = [0_u8; 3];
+            for (i, channel) in channels.iter_mut().enumerate() {
+                *channel = (from_rgb[i] as f32 + (to_rgb[i] as f32 - from_rgb[i] as f32) * t).round() as u8;
+            }
+            format!("#{:02x}{:02x}{:02x}", channels[0], channels[1], channels[2])
+        }
+        _ if t < 0.5 => String::from(from),
+        _ => String::from(to),
+    }
+}
+
+/// Compute the relative luminance of a color given as a `#rgb` or `#rrggbb` hex string,
+/// as defined by the [WCAG](https://www.w3.org/TR/WCAG20/#relativeluminancedef).
+pub(crate) fn relative_luminance(hex: &str) -> Option<f32> {
+    let channels = parse_rgb(hex)?.map(|channel| {
+        let channel = channel as f32 / 255_f32;
+        if channel <= 0.03928 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+
+    Some(0.2126 * channels[0] + 0.7152 * channels[1] + 0.0722 * channels[2])
+}
+
+/// Compute the contrast ratio between two colors, ranging from 1 (no contrast) to 21.
+pub(crate) fn contrast_ratio(first_hex: &str, second_hex: &str) -> Option<f32> {
+    let first = relative_luminance(first_hex)?;
+    let second = relative_luminance(second_hex)?;
+
+    Some((first.max(second) + 0.05) / (first.min(second) + 0.05))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_between_hex_colors() {
+        assert_eq!(interpolate_color("#000000", "#ffffff", 0_f32), "#000000");
+        assert_eq!(interpolate_color("#000000", "#ffffff", 0.5), "#808080");
+        assert_eq!(interpolate_color("#000000", "#ffffff", 1_f32), "#ffffff");
+        assert_eq!(interpolate_color("#ff0000", "#00f", 0.25), "#bf0040");
+    }
+
+    #[test]
+    fn interpolate_clamps_out_of_range_fractions() {
+        assert_eq!(interpolate_color("#000000", "#ffffff", -1_f32), "#000000");
+        assert_eq!(interpolate_color("#000000", "#ffffff", 2_f32), "#ffffff");
+        assert_eq!(interpolate_color("#000000", "not a color", 0.25), "#000000");
+    }
+
+    #[test]
+    fn luminance_of_hex_colors() {
+        assert_eq!(relative_luminance("#000000"), Some(0_f32));
+        assert!((relative_luminance("#fff").unwrap() - 1_f32).abs() < 1e-6);
+        assert_eq!(relative_luminance("#ffffff"), relative_luminance("#FFF"));
+        assert_eq!(relative_luminance("not a color"), None);
+        assert_eq!(parse_rgb("#1f77b4"), Some([0x1f, 0x77, 0xb4]));
+        assert!((contrast_ratio("#000", "#fff").unwrap() - 21_f32).abs() < 1e-4);
+    }
+}
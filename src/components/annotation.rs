@@ -0,0 +1,75 @@
+use svg::node::Node;
+use svg::node::Text as TextNode;
+use svg::node::element::{Circle, Group, Line, Text};
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+
+/// The space between the text of an annotation and what it points at.
+const TEXT_PADDING: f32 = 6_f32;
+
+/// Represents a callout at a point of the chart, made of a marker,
+/// a text above it and optionally a leader line between the two.
+#[derive(Debug)]
+pub struct Annotation {
+    x: f32,
+    y: f32,
+    text: String,
+    leader_line_length: Option<f32>,
+}
+
+impl Annotation {
+    pub fn new(x: f32, y: f32, text: String, leader_line_length: Option<f32>) -> Self {
+        Self {
+            x,
+            y,
+            text,
+            leader_line_length,
+        }
+    }
+}
+
+impl DatumRepresentation for Annotation {
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let leader_line_length = self.leader_line_length.unwrap_or(0_f32);
+
+        let mut group = Group::new()
+            .set("transform", format!("translate({},{})", self.x, self.y))
+            .set("class", "annotation");
+
+        group.append(
+            Circle::new()
+                .set("class", "annotation-marker")
+                .set("cx", 0)
+                .set("cy", 0)
+                .set("r", 4)
+                .set("fill", "#333")
+        );
+
+        if leader_line_length > 0_f32 {
+            group.append(
+                Line::new()
+                    .set("class", "annotation-leader")
+                    .set("x1", 0)
+                    .set("y1", 0)
+                    .set("x2", 0)
+                    .set("y2", -leader_line_length)
+                    .set("stroke", "#333")
+                    .set("stroke-width", 1)
+            );
+        }
+
+        group.append(
+            Text::new()
+                .set("class", "annotation-text")
+                .set("x", 0)
+                .set("y", -(leader_line_length + TEXT_PADDING))
+                .set("text-anchor", "middle")
+                .set("font-family", "sans-serif")
+                .set("font-size", "12px")
+                .set("fill", "#333")
+                .add(TextNode::new(self.text.as_str()))
+        );
+
+        Ok(group)
+    }
+}
@@ -3,13 +3,83 @@ use svg::node::element::{Group, Path};
 use svg::node::element::path::Data;
 use svg::node::Node;
 use crate::components::DatumRepresentation;
+use crate::ChartError;
 use crate::components::scatter::ScatterPoint;
 
-/// Represents a point in a scatter plot.
+/// The way in which a line, or the top of an area, connects its points.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Curve {
+    /// A straight line from each point to the next.
+    Linear,
+    /// A vertical line to the value of the next point, followed by a horizontal line to it.
+    StepBefore,
+    /// A horizontal line at the value of each point up to the next, followed by a vertical line to it.
+    StepAfter,
+    /// A smooth curve through all the points, of which the tension runs from 0 for
+    /// a Catmull-Rom spline to 1 for straight lines.
+    CatmullRom(f32),
+}
+
+impl Curve {
+    /// Draw the path through the points, starting with a move to the first one.
+    pub(crate) fn draw(&self, points: &[(f32, f32)]) -> Data {
+        let mut data = Data::new();
+        if let Some(first) = points.first() {
+            data = data.move_to(*first);
+        }
+
+        self.connect(data, points)
+    }
+
+    /// The curve that draws the same line when its points are walked in reverse,
+    /// which mirrors the steps.
+    fn reversed(&self) -> Curve {
+        match self {
+            Curve::StepBefore => Curve::StepAfter,
+            Curve::StepAfter => Curve::StepBefore,
+            curve => *curve,
+        }
+    }
+
+    /// Continue the path from the first point through the rest of the points, which is
+    /// expected to be where the path already is.
+    fn connect(&self, mut data: Data, points: &[(f32, f32)]) -> Data {
+        for i in 1..points.len() {
+            let (previous, point) = (points[i - 1], points[i]);
+            data = match self {
+                Curve::Linear => data.line_to(point),
+                Curve::StepBefore => data.line_to((previous.0, point.1)).line_to(point),
+                Curve::StepAfter => data.line_to((point.0, previous.1)).line_to(point),
+                Curve::CatmullRom(tension) => {
+                    // The missing neighbours of the first and last point are the points themselves.
+                    let before = points[i.saturating_sub(2)];
+                    let after = *points.get(i + 1).unwrap_or(&point);
+                    let k = (1_f32 - tension) / 6_f32;
+
+                    data.cubic_curve_to((
+                        previous.0 + (point.0 - before.0) * k,
+                        previous.1 + (point.1 - before.1) * k,
+                        point.0 - (after.0 - previous.0) * k,
+                        point.1 - (after.1 - previous.1) * k,
+                        point.0,
+                        point.1,
+                    ))
+                },
+            };
+        }
+
+        data
+    }
+}
+
+/// Represents the filled area below a series of points. Unless a baseline is set, the
+/// last two points close the area along the baseline, from the last to the first point.
 #[derive(Debug)]
 pub struct AreaSeries<T: Display + Clone, U: Display + Clone> {
     points: Vec<ScatterPoint<T, U>>,
     color: String,
+    curve: Curve,
+    baseline: Vec<(f32, f32)>,
 }
 
 impl<T: Display + Clone, U: Display + Clone> AreaSeries<T, U> {
@@ -20,24 +90,48 @@ impl<T: Display + Clone, U: Display + Clone> AreaSeries<T, U> {
         Self {
             points,
             color,
+            curve: Curve::Linear,
+            baseline: Vec::new(),
         }
     }
+
+    /// Set the lower boundary of the area, from the first to the last point, e.g. the top of
+    /// the layer below it in a stack. It is connected with the same curve as the top.
+    pub fn set_baseline(mut self, baseline: Vec<(f32, f32)>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Set the way in which the top of the area connects its points.
+    pub fn set_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
 }
 
 impl<T: Display + Clone, U: Display + Clone> DatumRepresentation for AreaSeries<T, U> {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        if self.points.is_empty() {
+            return Err(ChartError::EmptyData);
+        }
+
         let mut group = Group::new()
             .set("class", "line");
 
-        let mut data = Data::new();
-
-        for (i, point) in self.points.iter().enumerate() {
-            if i == 0 {
-                data = data.move_to((point.get_x(), point.get_y()));
-            } else {
-                data = data.line_to((point.get_x(), point.get_y()));
+        let coordinates = self.points.iter().map(|point| (point.get_x(), point.get_y())).collect::<Vec<(f32, f32)>>();
+        let mut data;
+        if self.baseline.is_empty() {
+            // The baseline points are always connected by straight lines.
+            let top_count = usize::max(coordinates.len().saturating_sub(2), 1);
+            data = self.curve.draw(&coordinates[..top_count]);
+            for point in coordinates[top_count..].iter() {
+                data = data.line_to(*point);
             }
+        } else {
+            let baseline = self.baseline.iter().rev().copied().collect::<Vec<(f32, f32)>>();
+            data = self.curve.draw(&coordinates).line_to(baseline[0]);
+            data = self.curve.reversed().connect(data, &baseline);
         }
 
         data = data.close();
@@ -56,3 +150,54 @@ impl<T: Display + Clone, U: Display + Clone> DatumRepresentation for AreaSeries<
         Ok(group)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::scatter::{MarkerType, PointLabelPosition};
+
+    fn area_path(curve: Curve) -> String {
+        let points = vec![(0_f32, 100_f32), (50_f32, 50_f32), (100_f32, 80_f32), (100_f32, 200_f32), (0_f32, 200_f32)]
+            .into_iter()
+            .map(|(x, y)| ScatterPoint::new(x, y, MarkerType::Circle, 5, x, y, PointLabelPosition::NW, false, false, String::from("#fff")))
+            .collect::<Vec<ScatterPoint<f32, f32>>>();
+        let svg = AreaSeries::new(points, String::from("#1f77b4")).set_curve(curve).to_svg().unwrap().to_string();
+
+        svg.split(" d=\"").nth(1).unwrap().split('"').next().unwrap().to_owned()
+    }
+
+    #[test]
+    fn step_after_area_closes_to_baseline() {
+        assert_eq!(area_path(Curve::StepAfter), "M0,100 L50,100 L50,50 L100,50 L100,80 L100,200 L0,200 z");
+    }
+
+    #[test]
+    fn step_before_and_linear_areas() {
+        assert_eq!(area_path(Curve::StepBefore), "M0,100 L0,50 L50,50 L50,80 L100,80 L100,200 L0,200 z");
+        assert_eq!(area_path(Curve::Linear), "M0,100 L50,50 L100,80 L100,200 L0,200 z");
+    }
+
+    #[test]
+    fn area_closes_along_its_baseline() {
+        let points = vec![(0_f32, 100_f32), (50_f32, 50_f32), (100_f32, 80_f32)]
+            .into_iter()
+            .map(|(x, y)| ScatterPoint::new(x, y, MarkerType::Circle, 5, x, y, PointLabelPosition::NW, false, true, String::from("#fff")))
+            .collect::<Vec<ScatterPoint<f32, f32>>>();
+        let svg = AreaSeries::new(points, String::from("#1f77b4"))
+            .set_curve(Curve::StepAfter)
+            .set_baseline(vec![(0_f32, 150_f32), (50_f32, 120_f32), (100_f32, 130_f32)])
+            .to_svg()
+            .unwrap()
+            .to_string();
+
+        // The baseline runs back from the last point along the same steps as it would forwards.
+        assert!(svg.contains("d=\"M0,100 L50,100 L50,50 L100,50 L100,80 L100,130 L100,120 L50,120 L50,150 L0,150 z\""));
+    }
+
+    #[test]
+    fn empty_area_is_an_error() {
+        let area = AreaSeries::<f32, f32>::new(Vec::new(), String::from("#1f77b4"));
+
+        assert_eq!(area.to_svg().unwrap_err(), ChartError::EmptyData);
+    }
+}
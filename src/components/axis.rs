@@ -6,6 +6,7 @@ use format_num::NumberFormat;
 use crate::axis::AxisPosition;
 
 /// A simple struct that represents an axis line.
+#[derive(Clone)]
 pub(crate) struct AxisLine {
     x1: f32,
     y1: f32,
@@ -19,6 +20,15 @@ impl AxisLine {
         Self { x1, y1, x2, y2 }
     }
 
+    /// Return a copy of the axis line that spans only between the given offsets along its direction.
+    pub fn trimmed(&self, start: f32, end: f32) -> Self {
+        if self.y1 == self.y2 {
+            Self::new(start, self.y1, end, self.y2)
+        } else {
+            Self::new(self.x1, start, self.x2, end)
+        }
+    }
+
     /// Render the axis line to svg.
     pub fn to_svg(&self) -> Result<Line, String> {
         let line = Line::new()
@@ -41,7 +51,9 @@ pub struct AxisTick {
     label_rotation: isize,
     tick_offset: f32,
     label: String,
-    label_format: Option<String>
+    label_format: Option<String>,
+    label_prefix: String,
+    label_suffix: String,
 }
 
 impl AxisTick {
@@ -54,6 +66,8 @@ impl AxisTick {
             label,
             axis_position,
             label_format: None,
+            label_prefix: String::new(),
+            label_suffix: String::new(),
         }
     }
 
@@ -67,14 +81,31 @@ impl AxisTick {
         self.label_format = Some(format.to_owned());
     }
 
+    /// Set the text before and after the formatted label, e.g. a currency or a unit.
+    pub fn set_label_affixes(&mut self, prefix: &str, suffix: &str) {
+        self.label_prefix = prefix.to_owned();
+        self.label_suffix = suffix.to_owned();
+    }
+
+    /// Return the offset of the tick along the axis.
+    pub fn get_tick_offset(&self) -> f32 {
+        self.tick_offset
+    }
+
+    /// Return the label of the tick formatted with the label format (if present), between its prefix and suffix.
+    /// Labels that are not numeric, e.g. those produced by a scale's tick formatter, are kept as they are.
+    pub fn formatted_label(&self) -> String {
+        let label = match (self.label_format.as_ref(), self.label.parse::<f64>()) {
+            (Some(format), Ok(value)) => NumberFormat::new().format(format, value).replace('G', "B"),
+            _ => self.label.to_owned(),
+        };
+
+        format!("{}{}{}", self.label_prefix, label, self.label_suffix)
+    }
+
     /// Render the axis tick to svg.
     pub fn to_svg(&self) -> Result<Group, String> {
-        let formatted_label = if self.label_format.is_some() {
-            let formatter = NumberFormat::new();
-            formatter.format(self.label_format.as_ref().unwrap(), self.label.parse::<f64>().unwrap()).replace('G', "B")
-        } else {
-            self.label.to_owned()
-        };
+        let formatted_label = self.formatted_label();
         let offsets: (f32, f32);
         let tick_line_p2: (isize, isize);
         let tick_label_offset: (isize, isize);
@@ -1,9 +1,13 @@
 use svg::node::Node;
 use svg::node::element::Group;
+use svg::node::element::Path;
+use svg::node::element::path::Data;
 use svg::node::element::Rectangle;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
+use svg::node::element::Title;
 use crate::components::DatumRepresentation;
+use crate::ChartError;
 use crate::chart::Orientation;
 
 /// Set the position of a bar's label.
@@ -16,15 +20,32 @@ pub enum BarLabelPosition {
     EndOutside,
 }
 
+/// Set how the keys of a category are laid out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BarLayout {
+    /// The blocks of the keys are stacked on top of each other in a single bar.
+    Stacked,
+    /// Each key has its own bar, side by side within the band of the category.
+    Grouped,
+}
+
 /// Represents a block within a bar.
-/// The first tuple element represents the starting position, the second
-/// one is the size of that block and the third one is the color.
+/// The first two tuple elements represent the starting and ending positions,
+/// the third one is the size of that block, the fourth one is the color,
+/// the fifth one is the key of the stacked entry the block represents and
+/// the last one is the fill opacity of the block (if any).
 #[derive(Debug)]
-pub struct BarBlock(f32, f32, f32, String);
+pub struct BarBlock(f32, f32, f32, String, String, Option<f32>);
 
 impl BarBlock {
-    pub fn new(start: f32, end: f32, size: f32, color: String) -> Self {
-        Self(start, end, size, color)
+    pub fn new(start: f32, end: f32, size: f32, color: String, key: String) -> Self {
+        Self(start, end, size, color, key, None)
+    }
+
+    /// Set the fill opacity of the block.
+    pub fn set_opacity(mut self, opacity: Option<f32>) -> Self {
+        self.5 = opacity;
+        self
     }
 }
 
@@ -35,6 +56,9 @@ pub struct Bar {
     label_position: BarLabelPosition,
     rounding_precision: Option<usize>,
     label_visible: bool,
+    total_label_visible: bool,
+    vertical_labels: bool,
+    corner_radius: Option<f32>,
     category: String,
     bar_width: f32,
     offset: f32,
@@ -57,16 +81,105 @@ impl Bar {
             label_position,
             rounding_precision,
             label_visible,
+            total_label_visible: false,
+            vertical_labels: false,
+            corner_radius: None,
             category,
             bar_width,
             offset,
         }
     }
+
+    /// Set whether a single label with the total of all the blocks should be displayed at the end of the bar.
+    pub fn set_total_label_visibility(mut self, total_label_visibility: bool) -> Self {
+        self.total_label_visible = total_label_visibility;
+        self
+    }
+
+    /// Set whether the labels of a vertical bar should be rotated to run along the bar,
+    /// centered within each block, so that they fit inside narrow bars.
+    pub fn set_vertical_labels(mut self, vertical_labels: bool) -> Self {
+        self.vertical_labels = vertical_labels;
+        self
+    }
+
+    /// Set the radius of the corners of the last block, which is the end of the bar. The radius
+    /// is clamped to half the width and half the length of the block.
+    pub fn set_corner_radius(mut self, corner_radius: Option<f32>) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
+
+    /// Describe a block by its category, its key (if any) and its value, e.g. for screen readers.
+    fn describe_block(&self, block: &BarBlock) -> String {
+        if block.4.is_empty() {
+            format!("{}: {}", self.category, self.format_label(block.2))
+        } else {
+            format!("{}, {}: {}", self.category, block.4, self.format_label(block.2))
+        }
+    }
+
+    /// Format a value label according to the rounding precision of the bar.
+    fn format_label(&self, value: f32) -> String {
+        match &self.rounding_precision {
+            None => value.to_string(),
+            Some(nr_of_digits) => format!("{:.1$}", value, nr_of_digits)
+        }
+    }
+}
+
+/// Generate the bands that connect the blocks of the same key in adjacent bars.
+/// Bars are connected in the order of their offset along the category axis, and
+/// each band reuses the color of the blocks it connects at a reduced opacity.
+pub fn segment_connectors(bars: &[Bar]) -> Group {
+    let mut group = Group::new()
+        .set("class", "bar-connectors");
+
+    let mut ordered_bars = bars.iter().collect::<Vec<&Bar>>();
+    ordered_bars.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+    for pair in ordered_bars.windows(2) {
+        let (current, next) = (pair[0], pair[1]);
+        let current_edge = current.offset + current.bar_width;
+
+        for block in current.blocks.iter() {
+            let next_block = match next.blocks.iter().find(|next_block| next_block.4 == block.4) {
+                Some(next_block) => next_block,
+                None => continue,
+            };
+
+            let corners = [
+                (current_edge, block.0),
+                (next.offset, next_block.0),
+                (next.offset, next_block.1),
+                (current_edge, block.1),
+            ];
+            let mut data = Data::new();
+            for (i, (category_position, value_position)) in corners.iter().enumerate() {
+                let point = match current.orientation {
+                    Orientation::Vertical => (*category_position, *value_position),
+                    Orientation::Horizontal => (*value_position, *category_position),
+                };
+                data = if i == 0 { data.move_to(point) } else { data.line_to(point) };
+            }
+
+            group.append(
+                Path::new()
+                    .set("class", "bar-connector")
+                    .set("fill", block.3.as_ref())
+                    .set("fill-opacity", 0.3)
+                    .set("stroke", "none")
+                    .set("d", data.close())
+            );
+        }
+    }
+
+    group
 }
 
 impl DatumRepresentation for Bar {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let (bar_group_offset_x, bar_group_offset_y) = {
             match self.orientation {
                 Orientation::Vertical => (self.offset, 0_f32),
@@ -83,20 +196,35 @@ impl DatumRepresentation for Bar {
             Orientation::Vertical => ("y", "x", "height", "width"),
         };
 
-        for block in self.blocks.iter() {
-            let block_rect = Rectangle::new()
+        for (i, block) in self.blocks.iter().enumerate() {
+            let mut block_rect = Rectangle::new()
                 .set(x_attr, block.0)
                 .set(y_attr, 0)
                 .set(width_attr, block.1 - block.0)
                 .set(height_attr, self.bar_width)
                 .set("shape-rendering", "crispEdges")
-                .set("fill", block.3.as_ref());
+                .set("fill", block.3.as_ref())
+                .set("aria-label", self.describe_block(block))
+                .add(Title::new().add(TextNode::new(self.describe_block(block))));
+
+            if let Some(opacity) = block.5 {
+                block_rect.assign("fill-opacity", opacity);
+            }
+
+            // Only the end of the bar is rounded, the blocks below it stay square to avoid gaps.
+            if let Some(corner_radius) = self.corner_radius.filter(|_| i + 1 == self.blocks.len()) {
+                let radius = corner_radius.min(self.bar_width / 2_f32).min((block.1 - block.0) / 2_f32);
+                block_rect.assign("rx", radius);
+                block_rect.assign("ry", radius);
+            }
 
             group.append(block_rect);
 
             // Display labels if needed.
             if self.label_visible {
+                let rotated = self.vertical_labels && self.orientation == Orientation::Vertical;
                 let (label_x_attr_value, text_anchor) = match self.label_position {
+                    _ if rotated => (block.0 + (block.1 - block.0) / 2_f32, "middle"),
                     BarLabelPosition::StartOutside if self.orientation == Orientation::Horizontal => (block.0 - 12_f32, "end"),
                     BarLabelPosition::StartOutside if self.orientation == Orientation::Vertical => (block.1 + 16_f32, "middle"),
                     BarLabelPosition::StartInside if self.orientation == Orientation::Horizontal => (block.0 + 12_f32, "start"),
@@ -110,12 +238,9 @@ impl DatumRepresentation for Bar {
                     _ => (0_f32, "middle"), // this is needed to get rid of compiler warning of exhaustively covering match pattern.
                 };
 
-                let label_text = match &self.rounding_precision {
-                    None => block.2.to_string(),
-                    Some(nr_of_digits) => format!("{:.1$}", block.2.to_string().parse::<f32>().unwrap(), nr_of_digits)
-                };
+                let label_text = self.format_label(block.2);
 
-                let label = Text::new()
+                let mut label = Text::new()
                     .set(x_attr, label_x_attr_value)
                     .set(y_attr, self.bar_width / 2_f32)
                     .set("text-anchor", text_anchor)
@@ -125,12 +250,114 @@ impl DatumRepresentation for Bar {
                     .set("font-size", "14px")
                     .add(TextNode::new(label_text));
 
+                if rotated {
+                    label.assign("transform", format!("rotate(-90 {} {})", self.bar_width / 2_f32, label_x_attr_value));
+                }
+
                 group.append(label);
             }
         }
 
+        // Display the total of the stacked blocks at the end of the bar if needed.
+        if self.total_label_visible && !self.blocks.is_empty() {
+            let total = self.blocks.iter().map(|block| block.2).sum::<f32>();
+            let (label_x_attr_value, text_anchor) = match self.orientation {
+                Orientation::Horizontal => (self.blocks.iter().map(|block| block.1).fold(f32::MIN, f32::max) + 12_f32, "start"),
+                Orientation::Vertical => (self.blocks.iter().map(|block| block.0).fold(f32::MAX, f32::min) - 16_f32, "middle"),
+            };
+
+            let total_label = Text::new()
+                .set("class", "bar-total")
+                .set(x_attr, label_x_attr_value)
+                .set(y_attr, self.bar_width / 2_f32)
+                .set("text-anchor", text_anchor)
+                .set("dy", ".35em")
+                .set("font-family", "sans-serif")
+                .set("fill", "#333")
+                .set("font-size", "14px")
+                .add(TextNode::new(self.format_label(total)));
+
+            group.append(total_label);
+        }
+
         // svg::save("bar-vert.svg", &group).unwrap();
 
         Ok(group)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar_svg(orientation: Orientation) -> String {
+        let blocks = vec![BarBlock::new(100_f32, 300_f32, 40_f32, String::from("#1f77b4"), String::new())];
+
+        Bar::new(blocks, orientation, String::from("1977"), BarLabelPosition::Center, false, None, 50_f32, 20_f32)
+            .to_svg()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn orientation_swaps_length_and_thickness() {
+        let vertical = bar_svg(Orientation::Vertical);
+        assert!(vertical.contains("transform=\"translate(20,0)\""));
+        assert!(vertical.contains("height=\"200\" shape-rendering=\"crispEdges\" width=\"50\" x=\"0\" y=\"100\""));
+
+        let horizontal = bar_svg(Orientation::Horizontal);
+        assert!(horizontal.contains("transform=\"translate(0,20)\""));
+        assert!(horizontal.contains("height=\"50\" shape-rendering=\"crispEdges\" width=\"200\" x=\"100\" y=\"0\""));
+    }
+
+    #[test]
+    fn corner_radius_rounds_the_end_of_the_bar() {
+        let blocks = vec![
+            BarBlock::new(300_f32, 400_f32, 25_f32, String::from("#1f77b4"), String::from("Cassette")),
+            BarBlock::new(100_f32, 300_f32, 50_f32, String::from("#ff7f0e"), String::from("8 - Track")),
+        ];
+        let svg = Bar::new(blocks, Orientation::Vertical, String::from("1977"), BarLabelPosition::Center, false, None, 50_f32, 0_f32)
+            .set_corner_radius(Some(4_f32))
+            .to_svg()
+            .unwrap()
+            .to_string();
+        let rects = svg.split("<rect").skip(1).collect::<Vec<&str>>();
+
+        // The lower block stays square, only the rect of the top block is rounded.
+        assert_eq!(rects.len(), 2);
+        assert!(!rects[0].contains("rx="));
+        assert!(rects[1].contains("fill=\"#ff7f0e\""));
+        assert!(rects[1].contains("rx=\"4\" ry=\"4\" shape-rendering=\"crispEdges\""));
+
+        let blocks = vec![BarBlock::new(380_f32, 400_f32, 5_f32, String::from("#1f77b4"), String::new())];
+        let svg = Bar::new(blocks, Orientation::Vertical, String::from("1977"), BarLabelPosition::Center, false, None, 50_f32, 0_f32)
+            .set_corner_radius(Some(40_f32))
+            .to_svg()
+            .unwrap()
+            .to_string();
+
+        // The radius is clamped to half the length of the block, which is shorter than the bar is wide.
+        assert!(svg.contains("rx=\"10\" ry=\"10\""));
+
+        let blocks = vec![BarBlock::new(50_f32, 650_f32, 60_f32, String::from("#1f77b4"), String::new())];
+        let svg = Bar::new(blocks, Orientation::Horizontal, String::from("1977"), BarLabelPosition::Center, false, None, 20_f32, 0_f32)
+            .set_corner_radius(Some(40_f32))
+            .to_svg()
+            .unwrap()
+            .to_string();
+
+        // Or to half the width of the bar, when the block is longer.
+        assert!(svg.contains("rx=\"10\" ry=\"10\""));
+    }
+
+    #[test]
+    fn connectors_of_bars_without_a_valid_offset() {
+        let bar = |offset: f32| {
+            let blocks = vec![BarBlock::new(100_f32, 300_f32, 40_f32, String::from("#1f77b4"), String::from("Cassette"))];
+            Bar::new(blocks, Orientation::Vertical, String::from("1977"), BarLabelPosition::Center, false, None, 50_f32, offset)
+        };
+
+        // A degenerate band scale yields NaN offsets, which must not abort the rendering.
+        let connectors = segment_connectors(&[bar(f32::NAN), bar(0_f32), bar(f32::NAN)]).to_string();
+        assert!(connectors.starts_with("<g class=\"bar-connectors\">"));
+    }
+}
@@ -0,0 +1,162 @@
+use svg::node::Node;
+use svg::node::element::{Circle, Group, Line, Rectangle};
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+use crate::stats::quantile;
+
+/// The factor of the interquartile range beyond which samples are considered outliers.
+const OUTLIER_IQR_FACTOR: f32 = 1.5;
+
+/// The statistics that summarize a sample in a box plot.
+/// The whiskers end at the furthest samples within 1.5 times the interquartile range
+/// from the box, the samples beyond that are outliers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxPlotSummary {
+    pub lower_whisker: f32,
+    pub first_quartile: f32,
+    pub median: f32,
+    pub third_quartile: f32,
+    pub upper_whisker: f32,
+    pub mean: f32,
+    pub outliers: Vec<f32>,
+}
+
+impl BoxPlotSummary {
+    /// Compute the summary of the samples, in any order. Samples that are NaN or infinite
+    /// are ignored. Returns `None` when there are no finite samples.
+    pub fn from_samples(samples: &[f32]) -> Option<Self> {
+        let mut sorted = samples.iter().cloned().filter(|sample| sample.is_finite()).collect::<Vec<f32>>();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f32::total_cmp);
+
+        let first_quartile = quantile(&sorted, 0.25);
+        let third_quartile = quantile(&sorted, 0.75);
+        let fence_distance = OUTLIER_IQR_FACTOR * (third_quartile - first_quartile);
+        let (lower_fence, upper_fence) = (first_quartile - fence_distance, third_quartile + fence_distance);
+        let within_fences = |value: &&f32| **value >= lower_fence && **value <= upper_fence;
+
+        Some(Self {
+            lower_whisker: *sorted.iter().find(within_fences)?,
+            first_quartile,
+            median: quantile(&sorted, 0.5),
+            third_quartile,
+            upper_whisker: *sorted.iter().rev().find(within_fences)?,
+            mean: sorted.iter().sum::<f32>() / sorted.len() as f32,
+            outliers: sorted.iter().filter(|value| !within_fences(value)).cloned().collect(),
+        })
+    }
+
+    /// Map every statistic of the summary, e.g. to convert them to positions on a scale.
+    pub fn map<F: Fn(f32) -> f32>(&self, f: F) -> Self {
+        Self {
+            lower_whisker: f(self.lower_whisker),
+            first_quartile: f(self.first_quartile),
+            median: f(self.median),
+            third_quartile: f(self.third_quartile),
+            upper_whisker: f(self.upper_whisker),
+            mean: f(self.mean),
+            outliers: self.outliers.iter().map(|outlier| f(*outlier)).collect(),
+        }
+    }
+}
+
+/// Represents a vertical box plot of a single sample.
+/// The summary is expressed in positions along the vertical axis.
+#[derive(Debug)]
+pub struct BoxPlot {
+    summary: BoxPlotSummary,
+    offset: f32,
+    width: f32,
+    color: String,
+    mean_visible: bool,
+}
+
+impl BoxPlot {
+    pub fn new(summary: BoxPlotSummary, offset: f32, width: f32, color: String) -> Self {
+        Self {
+            summary,
+            offset,
+            width,
+            color,
+            mean_visible: false,
+        }
+    }
+
+    /// Set whether the mean should be displayed as a dashed line within the box.
+    pub fn set_mean_visibility(mut self, mean_visibility: bool) -> Self {
+        self.mean_visible = mean_visibility;
+        self
+    }
+
+    /// Create a horizontal line across the box at the given position.
+    fn box_line(&self, class: &str, position: f32) -> Line {
+        Line::new()
+            .set("class", class)
+            .set("x1", 0)
+            .set("y1", position)
+            .set("x2", self.width)
+            .set("y2", position)
+            .set("stroke", "#333")
+            .set("stroke-width", 2)
+    }
+}
+
+impl DatumRepresentation for BoxPlot {
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let summary = &self.summary;
+        let center = self.width / 2_f32;
+        let box_top = f32::min(summary.first_quartile, summary.third_quartile);
+        let box_height = (summary.third_quartile - summary.first_quartile).abs();
+
+        let mut group = Group::new()
+            .set("transform", format!("translate({},{})", self.offset, 0))
+            .set("class", "box-plot");
+
+        group.append(
+            Line::new()
+                .set("class", "box-whisker")
+                .set("x1", center)
+                .set("y1", summary.lower_whisker)
+                .set("x2", center)
+                .set("y2", summary.upper_whisker)
+                .set("stroke", "#333")
+                .set("stroke-width", 1)
+        );
+
+        group.append(
+            Rectangle::new()
+                .set("x", 0)
+                .set("y", box_top)
+                .set("width", self.width)
+                .set("height", box_height)
+                .set("shape-rendering", "crispEdges")
+                .set("fill", self.color.as_ref())
+                .set("stroke", "#333")
+        );
+
+        group.append(self.box_line("box-median", summary.median));
+
+        if self.mean_visible {
+            group.append(
+                self.box_line("box-mean", summary.mean)
+                    .set("stroke-dasharray", "4,2")
+            );
+        }
+
+        for outlier in summary.outliers.iter() {
+            group.append(
+                Circle::new()
+                    .set("class", "box-outlier")
+                    .set("cx", center)
+                    .set("cy", *outlier)
+                    .set("r", 3)
+                    .set("fill", "none")
+                    .set("stroke", "#333")
+            );
+        }
+
+        Ok(group)
+    }
+}
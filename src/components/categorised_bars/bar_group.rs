@@ -1,5 +1,8 @@
 use std::slice::Iter;
 
+use svg::node::element::{Group, Line};
+use svg::Node;
+
 use super::{bar_label::BarLabel, BarPosition};
 
 #[allow(dead_code)]
@@ -13,6 +16,7 @@ pub struct BarGroup {
     margin_before: usize,
     margin_after: usize,
     margin_between: usize,
+    group_separators: bool,
     children: BarLabelChildren,
 }
 
@@ -23,6 +27,7 @@ impl Default for BarGroup {
             margin_before: Default::default(),
             margin_after: Default::default(),
             margin_between: Default::default(),
+            group_separators: Default::default(),
             children: BarLabelChildren::SubGroups(Vec::default()),
         }
     }
@@ -58,6 +63,57 @@ impl BarGroup {
             .with_margin_after(after)
     }
 
+    /// Draw a separator line halfway the gap between each pair of adjacent subgroups
+    pub fn with_group_separators(mut self, enabled: bool) -> Self {
+        self.group_separators = enabled;
+        self
+    }
+
+    /// The positions of the separators between the subgroups when laid out by `bar_positions`
+    /// in the given dimension, halfway the last bar of a subgroup and the first bar of the next
+    pub fn separator_positions(&self, dimension: usize) -> Vec<usize> {
+        if !self.group_separators {
+            return Vec::new();
+        }
+
+        let bars = self.bar_positions(dimension).collect::<Vec<_>>();
+        let mut separators = Vec::new();
+        let mut first_bar = 0;
+        let mut previous_end: Option<usize> = None;
+        for bar_count in self.groups().map(|subgroup| subgroup.labels().count()) {
+            if bar_count == 0 {
+                continue;
+            }
+            if let Some(end) = previous_end {
+                separators.push((end + bars[first_bar].position_start) / 2);
+            }
+            first_bar += bar_count;
+            previous_end = Some(bars[first_bar - 1].position_end);
+        }
+
+        separators
+    }
+
+    /// The separator lines between the subgroups, spanning `height` from the top
+    pub fn separators_to_svg(&self, dimension: usize, height: usize) -> Group {
+        let mut group = Group::new().set("class", "group-separators");
+
+        for position in self.separator_positions(dimension) {
+            group.append(
+                Line::new()
+                    .set("class", "group-separator")
+                    .set("x1", position)
+                    .set("y1", 0)
+                    .set("x2", position)
+                    .set("y2", height)
+                    .set("stroke", "#ccc")
+                    .set("stroke-width", 1),
+            );
+        }
+
+        group
+    }
+
     pub fn define_groups<I: IntoIterator<Item = BarGroup>>(mut self, groups: I) -> Self {
         let mut subgroups = Vec::new();
         for group in groups.into_iter() {
@@ -88,13 +144,43 @@ impl BarGroup {
         BarGroupIterator::new(self)
     }
 
+    /// Lay out the bars to fill the dimension. The pixels that remain after flooring
+    /// the bar width are divided over the first bars, one pixel each.
     pub fn bar_positions(&self, dimension: usize) -> BarPositionIterator {
         let bar_width = self.calculate_bar_width(dimension);
+        let remainder = dimension.saturating_sub(self.width_for_bar_width(bar_width));
+
+        BarPositionIterator::new(
+            self,
+            1 + self.margin_before,
+            bar_width,
+            remainder,
+            self.margin_between,
+            self.margin_after,
+        )
+    }
+
+    /// Lay out the bars like `bar_positions`, but from right to left: the first bar
+    /// ends at the dimension and the margins are mirrored.
+    pub fn bar_positions_rev(&self, dimension: usize) -> impl Iterator<Item = BarPosition> + '_ {
+        let bar_width = self.calculate_bar_width(dimension);
+        let width = usize::max(dimension, self.width_for_bar_width(bar_width));
 
+        self.bar_positions(dimension).map(move |bar| BarPosition {
+            key: bar.key,
+            position_start: width + 1 - bar.position_end,
+            position_end: width + 1 - bar.position_start,
+        })
+    }
+
+    /// Lay out the bars at the given bar width, regardless of the total dimension available.
+    /// The total dimension needed is given by `width_for_bar_width`.
+    pub fn bar_positions_fixed(&self, bar_width: usize) -> BarPositionIterator<'_> {
         BarPositionIterator::new(
             self,
             1 + self.margin_before,
             bar_width,
+            0,
             self.margin_between,
             self.margin_after,
         )
@@ -121,20 +207,50 @@ impl BarGroup {
     }
 
     pub fn margin_total(&self) -> usize {
+        let label_margins = match &self.children {
+            BarLabelChildren::SubGroups(_) => 0,
+            BarLabelChildren::Labels(labels) => labels
+                .iter()
+                .fold(0, |margins, label| margins + label.margin_before() + label.margin_after()),
+        };
+
         self.margin_before
-            + self.margin_between * usize::max(self.child_count() - 1, 0)
+            + self.margin_between * self.child_count().saturating_sub(1)
+            + label_margins
             + self.margin_after
     }
 
+    /// The widest bar width at which the group, including the margins of all nested
+    /// groups, fits in the total width. The inverse of `width_for_bar_width`.
+    pub fn bar_width_for_total(&self, total: usize) -> usize {
+        let margins = self.width_for_bar_width(0);
+        let number_of_labels = self.labels().count();
+        if number_of_labels == 0 {
+            return 0;
+        }
+
+        total.saturating_sub(margins) / number_of_labels
+    }
+
+    /// Whether the margins leave room for the bars in the dimension, so that it can
+    /// be checked before the bar width is calculated.
+    pub fn margins_fit(&self, dimension: usize) -> bool {
+        self.margin_dimension() < dimension
+    }
+
     pub fn calculate_bar_width(&self, dimension: usize) -> usize {
-        let margin_dimension = self.groups().fold(self.margin_total(), |dimension, sg| {
-            dimension + sg.margin_total()
-        });
         let number_of_labels = self.labels().count();
-        let width = (dimension - margin_dimension) as f32 / number_of_labels as f32;
+        let width = (dimension - self.margin_dimension()) as f32 / number_of_labels as f32;
 
         f32::floor(width) as usize
     }
+
+    /// The margins of the group and of its direct subgroups together
+    fn margin_dimension(&self) -> usize {
+        self.groups().fold(self.margin_total(), |dimension, sg| {
+            dimension + sg.margin_total()
+        })
+    }
 }
 
 pub struct BarGroupIterator<'bli> {
@@ -232,6 +348,8 @@ pub struct BarPositionIterator<'bli> {
     subgroup_label_dimensions_iter: Option<Box<BarPositionIterator<'bli>>>,
     labels_iter: Option<Iter<'bli, BarLabel>>,
     bar_width: usize,
+    /// The number of bars that are still to be widened by a pixel.
+    extra_pixels: usize,
     position: usize,
     margin_between: usize,
     margin_after: usize,
@@ -242,6 +360,7 @@ impl<'bli> BarPositionIterator<'bli> {
         group: &'bli BarGroup,
         position: usize,
         bar_width: usize,
+        extra_pixels: usize,
         margin_between: usize,
         margin_after: usize,
     ) -> Self {
@@ -256,11 +375,18 @@ impl<'bli> BarPositionIterator<'bli> {
             labels_iter,
             position,
             bar_width,
+            extra_pixels,
             margin_between,
             margin_after,
         }
     }
 
+    /// The bar width of this layout, so that overlays can match it. The first bars
+    /// may be a pixel wider to fill the dimension, see `BarPosition::width`.
+    pub fn bar_width(&self) -> usize {
+        self.bar_width
+    }
+
     fn next_group(&mut self) -> Option<BarPosition> {
         if let Some(subgroups_iter) = self.subgroups_iter.as_mut() {
             if let Some(group) = subgroups_iter.next() {
@@ -268,6 +394,7 @@ impl<'bli> BarPositionIterator<'bli> {
                     group,
                     self.position + group.margin_before,
                     self.bar_width,
+                    self.extra_pixels,
                     group.margin_between,
                     group.margin_after,
                 )));
@@ -290,6 +417,7 @@ impl<'bli> BarPositionIterator<'bli> {
         if let Some(subgroup_labels_iter) = self.subgroup_label_dimensions_iter.as_deref_mut() {
             if let Some(label) = subgroup_labels_iter.next() {
                 self.position = label.position_end + 1;
+                self.extra_pixels = subgroup_labels_iter.extra_pixels;
                 Some(label)
             } else {
                 self.position += subgroup_labels_iter.margin_after + self.margin_between;
@@ -308,12 +436,19 @@ impl<'bli> Iterator for BarPositionIterator<'bli> {
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(labels_iter) = self.labels_iter.as_mut() {
             if let Some(label) = labels_iter.next() {
+                let bar_width = if self.extra_pixels > 0 {
+                    self.extra_pixels -= 1;
+                    self.bar_width + 1
+                } else {
+                    self.bar_width
+                };
+                self.position += label.margin_before();
                 let result = Some(BarPosition {
                     key: label.key,
                     position_start: self.position,
-                    position_end: self.position + self.bar_width - 1,
+                    position_end: self.position + bar_width - 1,
                 });
-                self.position += self.bar_width + self.margin_between;
+                self.position += bar_width + label.margin_after() + self.margin_between;
 
                 result
             } else {
@@ -4,6 +4,30 @@ use std::fmt::Display;
 pub struct BarLabel {
     pub key: usize,
     pub label: String,
+    margin_before: usize,
+    margin_after: usize,
+}
+
+impl BarLabel {
+    /// Leave `before` extra pixels in front of this bar, e.g. to set a total apart from its group
+    pub fn with_margin_before(mut self, before: usize) -> Self {
+        self.margin_before = before;
+        self
+    }
+
+    /// Leave `after` extra pixels behind this bar
+    pub fn with_margin_after(mut self, after: usize) -> Self {
+        self.margin_after = after;
+        self
+    }
+
+    pub fn margin_before(&self) -> usize {
+        self.margin_before
+    }
+
+    pub fn margin_after(&self) -> usize {
+        self.margin_after
+    }
 }
 
 impl From<usize> for BarLabel {
@@ -11,6 +35,7 @@ impl From<usize> for BarLabel {
         BarLabel {
             key,
             label: format!("{}", key),
+            ..Default::default()
         }
     }
 }
@@ -20,6 +45,7 @@ impl<D: Display> From<(usize, D)> for BarLabel {
         BarLabel {
             key: data.0 as usize,
             label: format!("{}", data.1),
+            ..Default::default()
         }
     }
 }
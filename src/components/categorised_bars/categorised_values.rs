@@ -1,12 +1,16 @@
 use std::{
-    collections::{btree_map::Iter, BTreeMap},
+    cmp::Ordering,
+    collections::{btree_map::Iter, BTreeMap, BTreeSet},
     fmt::Display,
     hash::Hash,
+    iter::Rev,
     ops::AddAssign,
 };
 
-use super::{categorised_value::CategorisedValue, segmented_value::SegmentedValue};
+use super::{categorised_value::CategorisedValue, segmented_value::SegmentedValue, ToF64};
 use crate::components::OrderedSet;
+use crate::scales::{nice_step, Continuous};
+use crate::ScaleLinear;
 
 #[derive(Default)]
 /// Base for collecting values per category and optionally per segment
@@ -19,6 +23,12 @@ use crate::components::OrderedSet;
 ///
 /// The values must implement the [AddAssign], [Copy], [Default] and [Into]<[JsonValue]> traits.
 ///
+/// Categories and segments keep the order in which they are first defined, either by
+/// `with_categories` and `with_segments` or by the data. The values are stored by the index
+/// of that order, so data that is added in its final order is never sorted.
+/// Each value still costs an O(log n) insert in the index-keyed [BTreeMap], so adding
+/// n already ordered values is O(n log n) without any re-sorting.
+///
 /// # Example
 /// ```rust
 /// # use charts::CategorisedValues;
@@ -115,13 +125,11 @@ where
     ) -> Self {
         for def in collection.into_iter() {
             let bar_definition: CategorisedValue<CAT, SEG, VAL> = def.into();
-            let bar_index = self
-                .category_keys
-                .define_if_not_exist(&bar_definition.category_key);
-            let stack_index = self
-                .segment_keys
-                .define_if_not_exist(&bar_definition.segment_key);
-            self.add_to_category(bar_index, stack_index, bar_definition.value);
+            self.add_labelled(
+                &bar_definition.category_key,
+                &bar_definition.segment_key,
+                bar_definition.value,
+            );
         }
 
         self
@@ -131,6 +139,26 @@ where
         self.values.iter()
     }
 
+    /// Iterate over the categories like `categories`, from the last to the first
+    ///
+    /// The items are the same pairs of index and values, so they map onto their
+    /// labels with `category_index_to_label` as well.
+    pub fn categories_rev<'i>(&'i self) -> Rev<Iter<'i, usize, SegmentedValue<VAL>>> {
+        self.values.iter().rev()
+    }
+
+    /// Iterate over the category keys in their defined order
+    pub fn category_keys(&self) -> impl Iterator<Item = &CAT> {
+        self.category_keys.iter()
+    }
+
+    /// The values of the category with the given key, if it holds any
+    pub fn category(&self, key: &CAT) -> Option<&SegmentedValue<VAL>> {
+        self.category_keys
+            .index_of(key)
+            .and_then(|category_index| self.values.get(&category_index))
+    }
+
     /// Closure that maps category indices to their corresponding label value
     ///
     /// ```rust
@@ -193,6 +221,503 @@ where
         move |(segment_index, val)| (&self.segment_keys[*segment_index], val)
     }
 
+    /// The categories in their defined order, as they are laid out along the category axis
+    pub fn category_domain(&self) -> Vec<&CAT> {
+        self.category_keys.iter().collect()
+    }
+
+    /// The domain that a value axis needs to show the totals of all categories
+    ///
+    /// The domain always includes zero, as bars start there, and is extended
+    /// to the nearest multiples of a round tick step.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let scores = CategorisedValues::new().add_data(vec![("A", 42), ("B", 97), ("C", 63)]);
+    ///
+    /// assert_eq!(scores.value_domain(), (0.0, 100.0));
+    /// ```
+    pub fn value_domain(&self) -> (Continuous, Continuous)
+    where
+        VAL: ToF64,
+    {
+        Self::nice_domain(self.height_extent())
+    }
+
+    /// The domain that the value axes of several panels share, so that their bars are comparable
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let north = CategorisedValues::new().add_data(vec![("Q1", 12), ("Q2", 37)]);
+    /// let south = CategorisedValues::new().add_data(vec![("Q1", 140), ("Q2", 85)]);
+    ///
+    /// assert_eq!(north.value_domain(), (0.0, 40.0));
+    /// assert_eq!(CategorisedValues::shared_value_domain(&[&north, &south]), (0.0, 140.0));
+    /// ```
+    pub fn shared_value_domain(panels: &[&Self]) -> (Continuous, Continuous)
+    where
+        VAL: ToF64,
+    {
+        Self::nice_domain(panels.iter().map(|panel| panel.height_extent()).fold(
+            (0.0, 0.0),
+            |(min, max), (panel_min, panel_max)| (Continuous::min(min, panel_min), Continuous::max(max, panel_max)),
+        ))
+    }
+
+    /// The value scale that maps the shared domain of the panels onto the range, to pass to
+    /// the view and the value axis of every panel
+    pub fn shared_value_scale(panels: &[&Self], range: Vec<isize>) -> ScaleLinear
+    where
+        VAL: ToF64,
+    {
+        let (start, end) = Self::shared_value_domain(panels);
+
+        ScaleLinear::new().set_domain(vec![start, end]).set_range(range)
+    }
+
+    /// The lowest and highest category total, including zero
+    fn height_extent(&self) -> (Continuous, Continuous)
+    where
+        VAL: ToF64,
+    {
+        self.values.values().fold((0.0, 0.0), |(min, max), category| {
+            let height = category.height().to_f64() as Continuous;
+            (Continuous::min(min, height), Continuous::max(max, height))
+        })
+    }
+
+    fn nice_domain((min, max): (Continuous, Continuous)) -> (Continuous, Continuous) {
+        if min == max {
+            return (min, max);
+        }
+
+        let step = nice_step(min, max, 10);
+        ((min / step).floor() * step, (max / step).ceil() * step)
+    }
+
+    /// Sum of the values of all the segments of all the categories
+    pub fn grand_total(&self) -> VAL {
+        let mut total = VAL::default();
+        for category in self.values.values() {
+            total += category.height();
+        }
+
+        total
+    }
+
+    /// Total of the values of all the segments of a category, if it holds any
+    pub fn category_total(&self, category: &CAT) -> Option<VAL> {
+        self.category(category).map(|category| category.height())
+    }
+
+    /// The highest category total, if any category holds values
+    pub fn max_height(&self) -> Option<VAL>
+    where
+        VAL: PartialOrd,
+    {
+        Self::extreme(self.values.values().map(|category| category.height()), Ordering::Greater)
+    }
+
+    /// The lowest category total, if any category holds values
+    pub fn min_height(&self) -> Option<VAL>
+    where
+        VAL: PartialOrd,
+    {
+        Self::extreme(self.values.values().map(|category| category.height()), Ordering::Less)
+    }
+
+    /// The highest value of a single segment in any of the categories, e.g. to scale grouped bars
+    pub fn max_segment_value(&self) -> Option<VAL>
+    where
+        VAL: PartialOrd,
+    {
+        Self::extreme(
+            self.values.values().flat_map(|category| category.values().map(|(_, value)| *value)),
+            Ordering::Greater,
+        )
+    }
+
+    /// The first of the values compared to which no other value is `beyond`
+    fn extreme<I: Iterator<Item = VAL>>(values: I, beyond: Ordering) -> Option<VAL>
+    where
+        VAL: PartialOrd,
+    {
+        values.fold(None, |extreme, value| match extreme {
+            Some(current) if value.partial_cmp(&current) != Some(beyond) => Some(current),
+            _ => Some(value),
+        })
+    }
+
+    /// Number of segments that are defined, either explicitly or by the data
+    pub fn segment_count(&self) -> usize {
+        self.segment_keys.len()
+    }
+
+    /// Number of distinct segments that hold a value in any of the categories
+    pub fn populated_segment_count(&self) -> usize {
+        self.values
+            .values()
+            .flat_map(|category| category.values().map(|(segment_index, _)| *segment_index))
+            .collect::<BTreeSet<usize>>()
+            .len()
+    }
+
+    /// Add all the values of another collection into this one
+    ///
+    /// The categories and segments of the other collection that are not yet
+    /// defined in this one are appended in the order of the other collection.
+    pub fn merge(mut self, other: Self) -> Self {
+        for (category_index, category) in other.values.iter() {
+            for (segment_index, value) in category.values() {
+                self.add_labelled(
+                    &other.category_keys[*category_index],
+                    &other.segment_keys[*segment_index],
+                    *value,
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Split the collection in two based on a predicate on the segments
+    ///
+    /// The first collection contains the values of the segments for which the
+    /// predicate holds, the second one the values of all the other segments.
+    /// Both collections keep the order of the categories and segments of this one.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let (vinyl, other) = CategorisedValues::new()
+    ///     .add_data(vec![(1977, "LP/EP", 2_440_000), (1977, "Cassette", 450_000)])
+    ///     .partition_segments(|segment| segment.starts_with("LP"));
+    ///
+    /// assert_eq!(vinyl.to_string(), "{\n\t1977: 2440000\n }");
+    /// assert_eq!(other.to_string(), "{\n\t1977: 450000\n }");
+    /// ```
+    pub fn partition_segments<F: Fn(&SEG) -> bool>(self, predicate: F) -> (Self, Self) {
+        let mut matching = Self::new()
+            .with_categories(self.category_keys.iter().cloned())
+            .with_segments(self.segment_keys.iter().filter(|key| predicate(key)).cloned());
+        let mut rest = Self::new()
+            .with_categories(self.category_keys.iter().cloned())
+            .with_segments(self.segment_keys.iter().filter(|key| !predicate(key)).cloned());
+
+        for (category_index, category) in self.values.iter() {
+            for (segment_index, value) in category.values() {
+                let segment_key = &self.segment_keys[*segment_index];
+                let target = if predicate(segment_key) {
+                    &mut matching
+                } else {
+                    &mut rest
+                };
+                target.add_labelled(&self.category_keys[*category_index], segment_key, *value);
+            }
+        }
+
+        (matching, rest)
+    }
+
+    /// Remove the segments that hold no value other than zero in any of the categories
+    ///
+    /// The remaining segments keep their order. This drops e.g. the segments that
+    /// were defined with `with_segments`, but were never given a value.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let sales = CategorisedValues::new()
+    ///     .with_segments(vec!["8 - Track", "DVD Audio", "Cassette"])
+    ///     .add_data(vec![(1977, "Cassette", 36_900_000), (1977, "8 - Track", 127_300_000)]);
+    /// assert_eq!(sales.segment_count(), 3);
+    ///
+    /// let sales = sales.drop_empty_segments();
+    /// assert_eq!(sales.segment_count(), 2);
+    /// ```
+    pub fn drop_empty_segments(self) -> Self
+    where
+        VAL: PartialEq,
+    {
+        let populated = self
+            .values
+            .values()
+            .flat_map(|category| category.values())
+            .filter(|(_, value)| **value != VAL::default())
+            .map(|(segment_index, _)| *segment_index)
+            .collect::<BTreeSet<usize>>();
+
+        let mut dropped = Self::new()
+            .with_categories(self.category_keys.iter().cloned())
+            .with_segments(populated.iter().map(|segment_index| self.segment_keys[*segment_index].clone()));
+        for (category_index, category) in self.values.iter() {
+            for (segment_index, value) in category.values() {
+                if populated.contains(segment_index) {
+                    dropped.add_labelled(&self.category_keys[*category_index], &self.segment_keys[*segment_index], *value);
+                }
+            }
+        }
+
+        dropped
+    }
+
+    /// Order the categories by a key derived from each category
+    ///
+    /// Categories with an equal key keep their current relative order.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let by_length = CategorisedValues::new()
+    ///     .add_data(vec![("three", 3_u8), ("two", 2), ("eleven", 11)])
+    ///     .order_categories_by(|word| word.len());
+    ///
+    /// assert_eq!(by_length.to_string(), "{\n\ttwo: 2,\n\tthree: 3,\n\televen: 11\n }");
+    /// ```
+    pub fn order_categories_by<K: Ord, F: Fn(&CAT) -> K>(self, key: F) -> Self {
+        let mut order = (0..self.category_keys.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|category_index| key(&self.category_keys[*category_index]));
+
+        self.reorder_categories(order)
+    }
+
+    /// Order the categories by their total value
+    ///
+    /// Categories with an equal total keep their current relative order.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let ranking = CategorisedValues::new()
+    ///     .add_data(vec![("B", 20_u16), ("C", 5), ("A", 30)])
+    ///     .sort_categories_by_value(true);
+    ///
+    /// assert_eq!(ranking.to_string(), "{\n\tA: 30,\n\tB: 20,\n\tC: 5\n }");
+    /// ```
+    pub fn sort_categories_by_value(self, descending: bool) -> Self
+    where
+        VAL: PartialOrd,
+    {
+        let height = |category_index: &usize| {
+            self.values
+                .get(category_index)
+                .map_or(VAL::default(), |category| category.height())
+        };
+        let mut order = (0..self.category_keys.len()).collect::<Vec<usize>>();
+        order.sort_by(|a, b| {
+            let ordering = height(a).partial_cmp(&height(b)).unwrap_or(Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        self.reorder_categories(order)
+    }
+
+    /// Keep the `n` categories with the highest totals and sum all the others into one
+    ///
+    /// The kept categories keep their order, followed by the `other_label` category
+    /// that holds the values of the other categories per segment. With no more than
+    /// `n` categories, the collection is returned as it is.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let top = CategorisedValues::new()
+    ///     .add_data(vec![("A", 5_u16), ("B", 30), ("C", 2), ("D", 20)])
+    ///     .top_n(2, "Other");
+    ///
+    /// assert_eq!(top.to_string(), "{\n\tB: 30,\n\tD: 20,\n\tOther: 7\n }");
+    /// ```
+    pub fn top_n(self, n: usize, other_label: CAT) -> Self
+    where
+        VAL: PartialOrd,
+    {
+        if self.category_keys.len() <= n {
+            return self;
+        }
+
+        let height = |category_index: &usize| {
+            self.values
+                .get(category_index)
+                .map_or(VAL::default(), |category| category.height())
+        };
+        let mut by_height = (0..self.category_keys.len()).collect::<Vec<usize>>();
+        by_height.sort_by(|a, b| height(b).partial_cmp(&height(a)).unwrap_or(Ordering::Equal));
+        let kept = by_height[..n].iter().cloned().collect::<BTreeSet<usize>>();
+
+        let mut top = Self::new()
+            .with_categories(
+                self.category_keys
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| kept.contains(index))
+                    .map(|(_, key)| key.clone()),
+            )
+            .with_segments(self.segment_keys.iter().cloned());
+        top.category_keys.define_if_not_exist(&other_label);
+        for (category_index, category) in self.values.iter() {
+            let category_key = if kept.contains(category_index) {
+                &self.category_keys[*category_index]
+            } else {
+                &other_label
+            };
+            for (segment_index, value) in category.values() {
+                top.add_labelled(category_key, &self.segment_keys[*segment_index], *value);
+            }
+        }
+
+        top
+    }
+
+    /// The share of each segment in the total of its category, in percent
+    ///
+    /// The segments of each category sum up to 100, except for categories with
+    /// a total of zero, of which all segments are zero.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let shares = CategorisedValues::new()
+    ///     .add_data(vec![(1977, "Cassette", 1), (1977, "Vinyl", 3)])
+    ///     .normalize_to_percent();
+    ///
+    /// assert_eq!(shares.to_string(), "{\n\t1977: { Cassette: 25, Vinyl: 75 }\n }");
+    /// ```
+    pub fn normalize_to_percent(&self) -> CategorisedValues<CAT, SEG, f32>
+    where
+        VAL: ToF64,
+    {
+        let mut percentages = CategorisedValues::new()
+            .with_categories(self.category_keys.iter().cloned())
+            .with_segments(self.segment_keys.iter().cloned());
+
+        for (category_index, category) in self.values.iter() {
+            let height = category.height().to_f64();
+            for (segment_index, value) in category.values() {
+                let percentage = if height == 0_f64 {
+                    0_f32
+                } else {
+                    (value.to_f64() / height * 100_f64) as f32
+                };
+                percentages.add_to_category(*category_index, *segment_index, percentage);
+            }
+        }
+
+        percentages
+    }
+
+    /// Rebuild the categories in the given order of their current indices
+    fn reorder_categories(mut self, order: Vec<usize>) -> Self {
+        let mut category_keys = OrderedSet::new();
+        let mut values = BTreeMap::new();
+        for category_index in order {
+            let ordered_index = category_keys.define_if_not_exist(&self.category_keys[category_index]);
+            if let Some(category) = self.values.remove(&category_index) {
+                values.insert(ordered_index, category);
+            }
+        }
+
+        self.category_keys = category_keys;
+        self.values = values;
+        self
+    }
+
+    /// Running total of the categories, in their defined order
+    ///
+    /// Each category holds the sum of its own total and those of all the categories
+    /// before it, in a single segment. This turns e.g. the counts of histogram bins
+    /// into a cumulative distribution to overlay as a line.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let cumulative = CategorisedValues::new()
+    ///     .add_data(vec![("0-10", 2_usize), ("10-20", 3), ("20-30", 5)])
+    ///     .cumulative();
+    ///
+    /// assert_eq!(cumulative.to_string(), "{\n\t0-10: 2,\n\t10-20: 5,\n\t20-30: 10\n }");
+    /// ```
+    pub fn cumulative(&self) -> Self {
+        let mut cumulative = Self::new().with_categories(self.category_keys.iter().cloned());
+        let segment_index = cumulative.segment_keys.define_if_not_exist(&SEG::default());
+
+        let mut running_total = VAL::default();
+        for category_index in 0..self.category_keys.len() {
+            if let Some(category) = self.values.get(&category_index) {
+                running_total += category.height();
+            }
+            cumulative.add_to_category(category_index, segment_index, running_total);
+        }
+
+        cumulative
+    }
+
+    /// Swap the roles of the categories and the segments
+    ///
+    /// The segments become the categories and each category a segment of all of them,
+    /// both in their defined order. This flips a chart of the collection, e.g. from
+    /// bars per year stacked by format to bars per format grouped by year.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let by_format = CategorisedValues::new()
+    ///     .add_data(vec![(1977, "8 - Track", 127), (1977, "Cassette", 37), (1978, "Cassette", 61)])
+    ///     .transposed();
+    ///
+    /// assert_eq!(by_format.to_string(), "{\n\t8 - Track: { 1977: 127 },\n\tCassette: { 1977: 37, 1978: 61 }\n }");
+    /// ```
+    pub fn transposed(&self) -> CategorisedValues<SEG, CAT, VAL> {
+        let mut transposed = CategorisedValues::new()
+            .with_categories(self.segment_keys.iter().cloned())
+            .with_segments(self.category_keys.iter().cloned());
+
+        for (category_index, category) in self.values.iter() {
+            for (segment_index, value) in category.values() {
+                transposed.add_to_category(*segment_index, *category_index, *value);
+            }
+        }
+
+        transposed
+    }
+
+    /// Sum the segments of each category into a single segment with the given label
+    ///
+    /// Every category keeps its total, which turns the values of a stacked bar view
+    /// into those of a simple bar view.
+    ///
+    /// ```rust
+    /// # use charts::CategorisedValues;
+    ///
+    /// let totals = CategorisedValues::new()
+    ///     .add_data(vec![(1977, "Cassette", 36_900_000), (1977, "8 - Track", 127_300_000)])
+    ///     .collapse_segments("Total");
+    ///
+    /// assert_eq!(totals.segment_count(), 1);
+    /// assert_eq!(totals.to_string(), "{\n\t1977: 164200000\n }");
+    /// ```
+    pub fn collapse_segments(self, label: SEG) -> Self {
+        let mut collapsed = Self::new().with_categories(self.category_keys.iter().cloned());
+        let segment_index = collapsed.segment_keys.define_if_not_exist(&label);
+
+        for (category_index, category) in self.values.iter() {
+            collapsed.add_to_category(*category_index, segment_index, category.height());
+        }
+
+        collapsed
+    }
+
+    fn add_labelled(&mut self, category_key: &CAT, segment_key: &SEG, value: VAL) {
+        let bar_index = self.category_keys.define_if_not_exist(category_key);
+        let stack_index = self.segment_keys.define_if_not_exist(segment_key);
+        self.add_to_category(bar_index, stack_index, value);
+    }
+
     fn add_to_category(&mut self, bar_index: usize, stack_index: usize, value: VAL) {
         self.values
             .entry(bar_index)
@@ -200,6 +725,35 @@ where
             .add(stack_index, value);
     }
 
+    /// JSON representation of a categorised values collection
+    ///
+    /// The categories are the keys of an object, in their defined order. With a single
+    /// segment each category holds its value, otherwise an object of its segments and their values.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Value
+    where
+        VAL: Into<serde_json::Value>,
+    {
+        let values_only = self.segment_keys.len() < 2;
+
+        let mut categories = serde_json::Map::new();
+        for (cat_label, cat) in self.categories().map(self.category_index_to_label()) {
+            let value = if values_only {
+                cat.values().map(|(_, val)| (*val).into()).next().unwrap_or_default()
+            } else {
+                serde_json::Value::Object(
+                    cat.values()
+                        .map(self.segment_index_to_label())
+                        .map(|(seg_label, val)| (seg_label.to_string(), (*val).into()))
+                        .collect(),
+                )
+            };
+            categories.insert(cat_label.to_string(), value);
+        }
+
+        serde_json::Value::Object(categories)
+    }
+
     /// String representation of a categorised values collection
     ///
     /// rust
@@ -458,6 +1012,15 @@ fn iterate_frequencies() {
     assert!(l_category.has_values());
 }
 
+#[test]
+fn domain_of_counted_frequencies() {
+    let categorised = CategorisedValues::new().add_data("hello world".chars().filter(|c| c.is_alphabetic()));
+
+    assert_eq!(categorised.value_domain(), (0.0, 3.0));
+    let shares = categorised.normalize_to_percent();
+    assert_eq!(shares.category(&'l').map(|category| category.height()), Some(100_f32));
+}
+
 #[test]
 fn dbg() {
     let categorised = CategorisedValues::new()
@@ -490,3 +1053,458 @@ fn to_string() {
 
     println!("{}", categorised);
 }
+
+#[test]
+fn merge_adds_values_by_label() {
+    assert_output_eq(
+        CategorisedValues::new()
+            .add_data(vec![(1, "x", 1)])
+            .merge(CategorisedValues::new().add_data(vec![(2, "x", 2), (1, "x", 3)])),
+        "{ 1: 4, 2: 2 }",
+    );
+}
+
+#[cfg(test)]
+fn labelled_cells<CAT, SEG, VAL>(
+    categorised_values: &CategorisedValues<CAT, SEG, VAL>,
+) -> Vec<(String, String, String)>
+where
+    CAT: Clone + Default + Display + Hash + Eq,
+    SEG: Clone + Default + Display + Hash + Eq,
+    VAL: AddAssign<VAL> + Copy + Default + Display,
+{
+    let mut cells = Vec::new();
+    for (cat_label, cat) in categorised_values
+        .categories()
+        .map(categorised_values.category_index_to_label())
+    {
+        for (seg_label, val) in cat.values().map(categorised_values.segment_index_to_label()) {
+            cells.push((cat_label.to_string(), seg_label.to_string(), val.to_string()));
+        }
+    }
+    cells.sort();
+
+    cells
+}
+
+#[test]
+fn partition_and_merge_segments() {
+    let categorised = CategorisedValues::new()
+        .with_segments(vec!["8 - Track", "LP/EP", "Cassette", "DVD Audio", "CD"])
+        .add_data(vec![
+            (1977_i16, "Cassette", 36_900_000_i32),
+            (1977, "8 - Track", 127_300_000),
+            (1978, "8 - Track", 133_600_000),
+            (1978, "Cassette", 61_300_000),
+            (2000, "CD", 942_500_000),
+            (2000, "DVD Audio", 1_000),
+            (2010, "CD", 253_000_000),
+        ]);
+    let original = labelled_cells(&categorised);
+
+    let (long, short) = categorised.partition_segments(|segment| segment.len() > 5);
+
+    assert!(labelled_cells(&long)
+        .iter()
+        .all(|(_, segment, _)| segment.len() > 5));
+    assert_eq!(
+        labelled_cells(&short),
+        vec![
+            (String::from("2000"), String::from("CD"), String::from("942500000")),
+            (String::from("2010"), String::from("CD"), String::from("253000000")),
+        ]
+    );
+    assert_eq!(labelled_cells(&long.merge(short)), original);
+}
+
+#[test]
+fn order_categories_alphabetically() {
+    assert_output_eq(
+        CategorisedValues::new()
+            .add_data("hello world".chars().filter(|c| c.is_alphabetic()))
+            .order_categories_by(|letter| *letter),
+        "{ d: 1, e: 1, h: 1, l: 3, o: 2, r: 1, w: 1 }",
+    );
+}
+
+#[test]
+fn order_categories_by_custom_key() {
+    assert_output_eq(
+        CategorisedValues::new()
+            .add_data(vec![
+                ("Cassette", "1977", 36_900_000_i32),
+                ("LP/EP", "1977", 344_000_000),
+                ("8 - Track", "1977", 127_300_000),
+                ("CD", "1983", 800_000),
+                ("Cassette", "1978", 61_300_000),
+            ])
+            .order_categories_by(|format| std::cmp::Reverse(format.len())),
+        "{ 8 - Track: { 1977: 127300000 }, Cassette: { 1977: 36900000, 1978: 61300000 }, LP/EP: { 1977: 344000000 }, CD: { 1983: 800000 } }",
+    );
+}
+
+#[test]
+fn defined_and_populated_segments() {
+    let categorised = CategorisedValues::new()
+        .with_segments(vec!["8 - Track", "LP/EP", "Cassette", "DVD Audio", "CD"])
+        .add_data(vec![
+            (1977, "Cassette", 36_900_000),
+            (1977, "8 - Track", 127_300_000),
+            (1978, "Cassette", 61_300_000),
+        ]);
+
+    assert_eq!(categorised.segment_count(), 5);
+    assert_eq!(categorised.populated_segment_count(), 2);
+    assert_eq!(CategorisedValues::<i8, i8, f32>::new().populated_segment_count(), 0);
+}
+
+#[test]
+fn cumulative_bin_counts() {
+    let bins = CategorisedValues::<String, usize, usize>::new()
+        .add_data(vec![
+            (String::from("0-10"), 2_usize),
+            (String::from("10-20"), 3),
+            (String::from("20-30"), 5),
+        ]);
+    let cumulative = bins.cumulative();
+
+    let totals = cumulative
+        .categories()
+        .map(|(_, category)| category.height())
+        .collect::<Vec<usize>>();
+    let grand_total = bins.categories().map(|(_, category)| category.height()).sum::<usize>();
+
+    assert_eq!(totals, vec![2, 5, 10]);
+    assert_eq!(totals.last(), Some(&grand_total));
+    assert_eq!(cumulative.populated_segment_count(), 1);
+}
+
+#[test]
+fn domains_of_categories_and_values() {
+    let categorised = CategorisedValues::new()
+        .add_data(vec![(1977, "Cassette", 36), (1977, "8 - Track", 61), (1978, "Cassette", 45)]);
+
+    assert_eq!(categorised.category_domain(), vec![&1977, &1978]);
+    assert_eq!(categorised.value_domain(), (0.0, 100.0));
+
+    let changes = CategorisedValues::new().add_data(vec![("A", -13.5_f32), ("B", 4.0)]);
+    assert_eq!(changes.value_domain(), (-14.0, 4.0));
+
+    assert_eq!(CategorisedValues::<i8, i8, f32>::new().value_domain(), (0.0, 0.0));
+}
+
+#[test]
+fn panels_share_value_scale() {
+    use crate::scales::IterableScale;
+
+    let panels = [
+        CategorisedValues::new().add_data(vec![("Jan", "North", 12), ("Jan", "South", 25), ("Feb", "North", 31)]),
+        CategorisedValues::new().add_data(vec![("Jan", "North", 97), ("Feb", "South", 148)]),
+        CategorisedValues::new().add_data(vec![("Jan", "North", 56), ("Feb", "North", 8)]),
+    ];
+    let panel_refs = panels.iter().collect::<Vec<_>>();
+
+    assert_eq!(panels[0].value_domain(), (0.0, 40.0));
+    assert_eq!(panels[2].value_domain(), (0.0, 60.0));
+    assert_eq!(CategorisedValues::shared_value_domain(&panel_refs), (0.0, 160.0));
+
+    use crate::scales::Scale;
+
+    let scale = CategorisedValues::shared_value_scale(&panel_refs, vec![300, 0]);
+    assert_eq!(scale.scale(&160.0), 0.0);
+    assert_eq!(scale.scale(&80.0), 150.0);
+}
+
+#[test]
+fn only_populated_segments_remain() {
+    let forced = CategorisedValues::new()
+        .with_segments(vec!["Vinyl", "Unused", "Cassette", "Silent"])
+        .add_data(vec![
+            ("1977", "Cassette", 36),
+            ("1978", "Vinyl", 12),
+            ("1978", "Silent", 0),
+            ("1978", "Cassette", 61),
+        ]);
+    assert_eq!(forced.segment_count(), 4);
+
+    let dropped = forced.drop_empty_segments();
+
+    assert_eq!(dropped.segment_count(), 2);
+    assert_eq!(dropped.populated_segment_count(), 2);
+    assert_output_eq(dropped, "{ 1977: { Cassette: 36 }, 1978: { Vinyl: 12, Cassette: 61 } }");
+}
+
+#[test]
+fn large_ordered_dataset_keeps_insertion_order() {
+    let data = (0..10_000_u32).rev().map(|category| (category, category % 7));
+    let categorised = CategorisedValues::new().add_data(data);
+
+    let expected = (0..10_000_u32).rev().collect::<Vec<u32>>();
+    assert_eq!(categorised.category_domain().into_iter().cloned().collect::<Vec<u32>>(), expected);
+    assert_eq!(
+        categorised.categories().map(|(category_index, _)| *category_index).collect::<Vec<usize>>(),
+        (0..10_000).collect::<Vec<usize>>()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_of_categories_only() {
+    let categorised = CategorisedValues::new().add_data(vec![("C", 10_u16), ("B", 20), ("A", 30)]);
+    let json = categorised.to_json().to_string();
+
+    assert_eq!(json, r#"{"C":10,"B":20,"A":30}"#);
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), categorised.to_json());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_of_categories_with_segments() {
+    let categorised = CategorisedValues::new()
+        .with_segments(vec!["8 - Track", "Cassette"])
+        .add_data(vec![
+            (1978, "Cassette", 61_300_000_u32),
+            (1977, "Cassette", 36_900_000),
+            (1977, "8 - Track", 127_300_000),
+        ]);
+    let json = categorised.to_json().to_string();
+    let parsed = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+    assert_eq!(parsed["1978"]["Cassette"].as_u64(), Some(61_300_000));
+    assert_eq!(parsed["1977"]["8 - Track"].as_u64(), Some(127_300_000));
+    assert_eq!(
+        parsed.as_object().unwrap().keys().collect::<Vec<&String>>(),
+        vec!["1978", "1977"]
+    );
+    assert_eq!(
+        parsed["1977"].as_object().unwrap().keys().collect::<Vec<&String>>(),
+        vec!["8 - Track", "Cassette"]
+    );
+}
+
+#[test]
+fn sort_letter_frequencies_by_value() {
+    let frequencies = CategorisedValues::new()
+        .add_data("hello world".chars().filter(|c| c.is_alphabetic()))
+        .sort_categories_by_value(true);
+
+    assert_output_eq(frequencies, "{ l: 3, o: 2, h: 1, e: 1, w: 1, r: 1, d: 1 }");
+
+    let frequencies = CategorisedValues::new()
+        .add_data("hello world".chars().filter(|c| c.is_alphabetic()))
+        .sort_categories_by_value(false);
+
+    assert_output_eq(frequencies, "{ h: 1, e: 1, w: 1, r: 1, d: 1, o: 2, l: 3 }");
+}
+
+#[test]
+fn top_categories_and_other() {
+    let top = CategorisedValues::new()
+        .add_data(vec![
+            ("CD", "US", 942),
+            ("Cassette", "US", 76),
+            ("Vinyl", "US", 3),
+            ("Vinyl", "UK", 1),
+            ("Download", "US", 1_000),
+            ("DVD Audio", "UK", 2),
+        ])
+        .top_n(2, "Other");
+
+    assert_eq!(top.category_domain(), vec![&"CD", &"Download", &"Other"]);
+    assert_eq!(top.category(&"Other").unwrap().height(), 76 + 3 + 1 + 2);
+    assert_output_eq(
+        top,
+        "{ CD: { US: 942 }, Download: { US: 1000 }, Other: { US: 79, UK: 3 } }",
+    );
+
+    let few = CategorisedValues::new().add_data(vec![("A", 1), ("B", 2)]).top_n(2, "Other");
+    assert_output_eq(few, "{ A: 1, B: 2 }");
+}
+
+#[test]
+fn segments_normalized_to_percent() {
+    let percentages = CategorisedValues::new()
+        .add_data(vec![
+            (1983, "Vinyl", 1_689.0_f32),
+            (1983, "CD", 17.2),
+            (1983, "Cassette", 1_810.9),
+            (1988, "Vinyl", 793.4),
+            (1988, "CD", 2_089.9),
+            (1988, "Cassette", 3_385.1),
+            (1990, "Vinyl", 0.0),
+            (1990, "CD", 0.0),
+        ])
+        .normalize_to_percent();
+
+    for (_, category) in percentages.categories() {
+        let total = category.values().map(|(_, value)| *value).sum::<f32>();
+        if category.height() == 0_f32 {
+            assert_eq!(total, 0_f32);
+        } else {
+            assert!((total - 100_f32).abs() < 1e-3, "{} does not sum to 100", total);
+        }
+    }
+    assert_eq!(percentages.category(&1990).unwrap().value_of_segment(0), Some(0_f32));
+}
+
+#[test]
+fn collapsed_segments_keep_category_totals() {
+    let categorised = CategorisedValues::new()
+        .with_segments(vec!["8 - Track", "LP/EP", "Cassette", "DVD Audio", "CD"])
+        .add_data(vec![
+            (1977_i16, "Cassette", 36_900_000_i32),
+            (1977, "8 - Track", 127_300_000),
+            (1978, "8 - Track", 133_600_000),
+            (1978, "Cassette", 61_300_000),
+            (2000, "CD", 942_500_000),
+            (2000, "DVD Audio", 1_000),
+            (2010, "CD", 253_000_000),
+        ]);
+    let totals = categorised
+        .categories()
+        .map(|(_, category)| category.height())
+        .collect::<Vec<i32>>();
+
+    let collapsed = categorised.collapse_segments("Total");
+
+    assert_eq!(collapsed.segment_count(), 1);
+    assert_eq!(collapsed.category_domain(), vec![&1977, &1978, &2000, &2010]);
+    for ((_, category), total) in collapsed.categories().zip(totals) {
+        assert_eq!(category.values().count(), 1);
+        assert_eq!(category.value_of_segment(0), Some(total));
+    }
+}
+
+#[test]
+fn grand_and_category_totals() {
+    let categorised = CategorisedValues::new()
+        .with_segments(vec!["8 - Track", "LP/EP", "Cassette", "DVD Audio", "CD"])
+        .add_data(vec![
+            (1977_i16, "Cassette", 36_900_000_i64),
+            (1977, "8 - Track", 127_300_000),
+            (1979, "8 - Track", 102_300_000),
+            (1978, "8 - Track", 133_600_000),
+            (1978, "Cassette", 61_300_000),
+            (1979, "Cassette", 78_500_000),
+        ])
+        .add_data(vec![
+            (2000_i16, "CD", 942_500_000),
+            (2000, "DVD Audio", 1_000),
+            (2000, "Cassette", 76_000_000),
+            (2010, "DVD Audio", 40_000),
+            (2010, "CD", 253_000_000),
+        ]);
+
+    assert_eq!(
+        categorised.grand_total(),
+        36_900_000 + 127_300_000 + 102_300_000 + 133_600_000 + 61_300_000 + 78_500_000
+            + 942_500_000 + 1_000 + 76_000_000 + 40_000 + 253_000_000
+    );
+    assert_eq!(categorised.category_total(&1977), Some(36_900_000 + 127_300_000));
+    assert_eq!(categorised.category_total(&1990), None);
+    assert_eq!(CategorisedValues::<i16, &str, i64>::new().grand_total(), 0);
+}
+
+#[test]
+fn extreme_heights_and_segment_values() {
+    let categorised = CategorisedValues::new()
+        .with_segments(vec!["8 - Track", "LP/EP", "Cassette", "DVD Audio", "CD"])
+        .add_data(vec![
+            (1977_i16, "Cassette", 36_900_000_i32),
+            (1977, "8 - Track", 127_300_000),
+            (1979, "8 - Track", 102_300_000),
+            (1978, "8 - Track", 133_600_000),
+            (1978, "Cassette", 61_300_000),
+            (1979, "Cassette", 78_500_000),
+        ])
+        .add_data(vec![
+            (2000_i16, "CD", 942_500_000),
+            (2000, "DVD Audio", 1_000),
+            (2000, "Cassette", 76_000_000),
+            (2010, "DVD Audio", 40_000),
+            (2010, "CD", 253_000_000),
+        ]);
+
+    assert_eq!(categorised.max_height(), categorised.category_total(&2000));
+    assert_eq!(categorised.max_height(), Some(942_500_000 + 1_000 + 76_000_000));
+    assert_eq!(categorised.min_height(), Some(36_900_000 + 127_300_000));
+    assert_eq!(categorised.max_segment_value(), Some(942_500_000));
+
+    let diverging = CategorisedValues::new().add_data(vec![("A", 3.5_f32), ("B", -2.0), ("C", 1.0)]);
+    assert_eq!(diverging.min_height(), Some(-2.0));
+
+    let empty = CategorisedValues::<i16, &str, i32>::new();
+    assert_eq!(empty.max_height(), None);
+    assert_eq!(empty.max_segment_value(), None);
+}
+
+#[test]
+fn iterate_categories_in_reverse() {
+    let categorised = CategorisedValues::new()
+        .with_categories(1970..2000_i16)
+        .with_segments(vec!["8 - Track", "LP/EP", "Cassette", "DVD Audio", "CD"])
+        .add_data(vec![
+            (1977_i16, "Cassette", 36_900_000_i32),
+            (1977, "8 - Track", 127_300_000),
+            (1979, "8 - Track", 102_300_000),
+            (1978, "8 - Track", 133_600_000),
+            (1978, "Cassette", 61_300_000),
+            (1979, "Cassette", 78_500_000),
+        ])
+        .add_data(vec![
+            (2000_i16, "CD", 942_500_000),
+            (2000, "DVD Audio", 1_000),
+            (2000, "Cassette", 76_000_000),
+            (2010, "DVD Audio", 40_000),
+            (2010, "CD", 253_000_000),
+        ]);
+
+    let mut categories = categorised
+        .categories_rev()
+        .map(categorised.category_index_to_label());
+
+    let (category_label, category) = categories.next().unwrap();
+    assert_eq!(*category_label, 2010);
+    assert_eq!(category.height(), 40_000 + 253_000_000);
+
+    let (category_label, _) = categories.next().unwrap();
+    assert_eq!(*category_label, 2000);
+
+    let forward = categorised.categories().map(|(index, _)| *index).collect::<Vec<usize>>();
+    let mut backward = categorised.categories_rev().map(|(index, _)| *index).collect::<Vec<usize>>();
+    backward.reverse();
+    assert_eq!(backward, forward);
+}
+
+#[test]
+fn transposed_swaps_categories_and_segments() {
+    let categorised = CategorisedValues::new().add_data(vec![
+        ("A", "x", 11_u16),
+        ("B", "y", 13),
+        ("C", "z", 17),
+        ("A", "y", 19),
+        ("B", "z", 23),
+        ("C", "x", 29),
+        ("A", "z", 31),
+        ("B", "x", 37),
+        ("C", "y", 41),
+        ("A", "y", 43),
+    ]);
+
+    let transposed = categorised.transposed();
+
+    assert_eq!(transposed.category_domain(), vec![&"x", &"y", &"z"]);
+    assert_eq!(transposed.segment_count(), categorised.category_domain().len());
+    assert_eq!(transposed.grand_total(), categorised.grand_total());
+    assert_eq!(
+        labelled_cells(&transposed)
+            .into_iter()
+            .map(|(category, segment, value)| (segment, category, value))
+            .collect::<BTreeSet<_>>(),
+        labelled_cells(&categorised).into_iter().collect()
+    );
+    assert_output_eq(
+        transposed,
+        "{ x: { A: 11, B: 37, C: 29 }, y: { A: 62, B: 13, C: 41 }, z: { A: 31, B: 23, C: 17 } }",
+    );
+}
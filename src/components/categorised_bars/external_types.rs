@@ -2,6 +2,8 @@ pub use super::bar_group::BarGroup;
 pub use super::bar_label::BarLabel;
 pub use super::categorised_value::CategorisedValue;
 pub use super::categorised_values::CategorisedValues;
+pub use super::series_set::SeriesSet;
+pub use super::to_f64::ToF64;
 
 #[derive(Debug)]
 pub struct BarPosition {
@@ -9,3 +11,10 @@ pub struct BarPosition {
     pub position_start: usize,
     pub position_end: usize,
 }
+
+impl BarPosition {
+    /// The width of the bar, including both its start and end position
+    pub fn width(&self) -> usize {
+        self.position_end + 1 - self.position_start
+    }
+}
@@ -6,6 +6,8 @@ mod categorised_value;
 mod segmented_value;
 
 mod categorised_values;
+mod series_set;
+mod to_f64;
 
 pub use external_types::*;
 
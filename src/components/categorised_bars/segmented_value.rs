@@ -1,6 +1,10 @@
 use std::{
-    collections::{btree_map::Iter, BTreeMap},
-    ops::AddAssign,
+    cmp::Ordering,
+    collections::{
+        btree_map::{IntoIter, Iter},
+        BTreeMap,
+    },
+    ops::{AddAssign, Div},
 };
 
 #[derive(Debug, Default)]
@@ -40,7 +44,130 @@ where
         self.magnitude
     }
 
+    /// Number of segments that hold a value
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// The average value of the segments, or the default value without any segments
+    pub fn mean(&self) -> VAL
+    where
+        VAL: Div<Output = VAL> + From<u8>,
+    {
+        if self.segments.is_empty() {
+            return VAL::default();
+        }
+
+        let mut count = VAL::default();
+        for _ in self.segments.iter() {
+            count += VAL::from(1);
+        }
+
+        self.magnitude / count
+    }
+
+    /// The highest value of a single segment, if any segment holds a value
+    pub fn max_segment(&self) -> Option<VAL>
+    where
+        VAL: PartialOrd,
+    {
+        self.segments.values().fold(None, |max, value| match max {
+            Some(max) if value.partial_cmp(&max) != Some(Ordering::Greater) => Some(max),
+            _ => Some(*value),
+        })
+    }
+
+    /// Each segment index with the sum of the values up to and including that segment,
+    /// e.g. the offsets at which the segments of a stacked bar end
+    pub fn cumulative<'s>(&'s self) -> impl Iterator<Item = (usize, VAL)> + 's {
+        self.segments.iter().scan(VAL::default(), |running_total, (segment_index, value)| {
+            *running_total += *value;
+            Some((*segment_index, *running_total))
+        })
+    }
+
     pub fn values<'s>(&'s self) -> Iter<'s, usize, VAL> {
         self.segments.iter()
     }
 }
+
+impl<VAL> IntoIterator for SegmentedValue<VAL>
+where
+    VAL: AddAssign<VAL> + Copy + Default,
+{
+    type Item = (usize, VAL);
+    type IntoIter = IntoIter<usize, VAL>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.segments.into_iter()
+    }
+}
+
+impl<'s, VAL> IntoIterator for &'s SegmentedValue<VAL>
+where
+    VAL: AddAssign<VAL> + Copy + Default,
+{
+    type Item = (&'s usize, &'s VAL);
+    type IntoIter = Iter<'s, usize, VAL>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values()
+    }
+}
+
+#[test]
+fn mean_of_segments() {
+    let mut category = SegmentedValue::default();
+    category.add(0, 127_300_000_i32);
+    category.add(2, 36_900_000);
+    category.add(4, 942_500_000);
+
+    assert_eq!(category.segment_count(), 3);
+    assert_eq!(category.mean(), (127_300_000 + 36_900_000 + 942_500_000) / 3);
+
+    let mut fractions = SegmentedValue::default();
+    fractions.add(0, 1.5_f32);
+    fractions.add(1, 2.0);
+    fractions.add(1, 0.5);
+    assert_eq!(fractions.segment_count(), 2);
+    assert_eq!(fractions.mean(), 2.0);
+
+    assert_eq!(SegmentedValue::<f32>::default().mean(), 0.0);
+}
+
+#[test]
+fn max_and_cumulative_segments() {
+    let mut category = SegmentedValue::default();
+    category.add(4, 942_500_000_i32);
+    category.add(0, 127_300_000);
+    category.add(2, 36_900_000);
+
+    assert_eq!(category.max_segment(), Some(942_500_000));
+    assert_eq!(
+        category.cumulative().collect::<Vec<(usize, i32)>>(),
+        vec![
+            (0, 127_300_000),
+            (2, 127_300_000 + 36_900_000),
+            (4, 127_300_000 + 36_900_000 + 942_500_000),
+        ]
+    );
+    assert_eq!(category.cumulative().last().map(|(_, total)| total), Some(category.height()));
+
+    assert_eq!(SegmentedValue::<i32>::default().max_segment(), None);
+    assert_eq!(SegmentedValue::<i32>::default().cumulative().count(), 0);
+}
+
+#[test]
+fn into_iterator_yields_segments_in_index_order() {
+    let mut category = SegmentedValue::default();
+    category.add(3, 61_300_000_i32);
+    category.add(0, 133_600_000);
+
+    let borrowed = (&category).into_iter().map(|(index, value)| (*index, *value)).collect::<Vec<(usize, i32)>>();
+    let expected = category.values().map(|(index, value)| (*index, *value)).collect::<Vec<(usize, i32)>>();
+    assert_eq!(borrowed, expected);
+
+    let consumed = category.into_iter().collect::<Vec<(usize, i32)>>();
+    assert_eq!(consumed, expected);
+    assert_eq!(consumed, vec![(0, 133_600_000), (3, 61_300_000)]);
+}
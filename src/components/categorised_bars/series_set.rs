@@ -0,0 +1,165 @@
+use std::{fmt::Display, hash::Hash, ops::AddAssign};
+
+use super::{categorised_values::CategorisedValues, segmented_value::SegmentedValue};
+use crate::components::OrderedSet;
+
+/// Collection of named series of categorised values that share their categories
+///
+/// Each series keeps its own segments and values, while the categories of all
+/// series are combined in a single ordered set. This allows e.g. a combo chart to
+/// draw bars for one series and a line for another at the same category positions.
+///
+/// # Example
+/// ```rust
+/// # use charts::{CategorisedValues, SeriesSet};
+///
+/// let series = SeriesSet::new()
+///     .with_series("sales", CategorisedValues::new().add_data(vec![(2019, 10), (2020, 12)]))
+///     .with_series("target", CategorisedValues::new().add_data(vec![(2020, 11), (2021, 13)]));
+///
+/// let years: Vec<i32> = series.aligned().map(|(year, _)| *year).collect();
+///
+/// assert_eq!(years, vec![2019, 2020, 2021]);
+/// ```
+pub struct SeriesSet<CAT, SEG, VAL>
+where
+    CAT: Clone + Default + Display + Hash + Eq,
+    SEG: Clone + Default + Display + Hash + Eq,
+    VAL: AddAssign<VAL> + Copy + Default + Display,
+{
+    category_keys: OrderedSet<CAT>,
+    series: Vec<(String, CategorisedValues<CAT, SEG, VAL>)>,
+}
+
+impl<CAT, SEG, VAL> Default for SeriesSet<CAT, SEG, VAL>
+where
+    CAT: Clone + Default + Display + Hash + Eq,
+    SEG: Clone + Default + Display + Hash + Eq,
+    VAL: AddAssign<VAL> + Copy + Default + Display,
+{
+    fn default() -> Self {
+        Self {
+            category_keys: OrderedSet::default(),
+            series: Vec::new(),
+        }
+    }
+}
+
+impl<CAT, SEG, VAL> SeriesSet<CAT, SEG, VAL>
+where
+    CAT: Clone + Default + Display + Hash + Eq,
+    SEG: Clone + Default + Display + Hash + Eq,
+    VAL: AddAssign<VAL> + Copy + Default + Display,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define the order of the shared categories
+    ///
+    /// Categories of series that are not part of this order are appended.
+    pub fn with_categories<I: IntoIterator<Item = CAT>>(mut self, keys: I) -> Self {
+        self.category_keys.clear();
+        for key in keys.into_iter() {
+            self.category_keys.define_if_not_exist(&key);
+        }
+        for (_, values) in self.series.iter() {
+            for key in values.category_keys() {
+                self.category_keys.define_if_not_exist(key);
+            }
+        }
+        self
+    }
+
+    /// Add a named series, appending its categories that are not yet defined
+    pub fn with_series(mut self, name: &str, values: CategorisedValues<CAT, SEG, VAL>) -> Self {
+        for key in values.category_keys() {
+            self.category_keys.define_if_not_exist(key);
+        }
+        self.series.push((name.to_owned(), values));
+        self
+    }
+
+    /// Number of series in the set
+    pub fn series_count(&self) -> usize {
+        self.series.len()
+    }
+
+    /// The names of the series, in the order they were added
+    pub fn series_names(&self) -> impl Iterator<Item = &str> {
+        self.series.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// The series with the given name
+    pub fn series(&self, name: &str) -> Option<&CategorisedValues<CAT, SEG, VAL>> {
+        self.series
+            .iter()
+            .find(|(series_name, _)| series_name == name)
+            .map(|(_, values)| values)
+    }
+
+    /// Iterate over the shared categories, together with the values of each series in that category
+    ///
+    /// The values are listed in the order of the series and are `None` for
+    /// a series that holds no values in the category.
+    pub fn aligned<'s>(
+        &'s self,
+    ) -> impl Iterator<Item = (&'s CAT, Vec<Option<&'s SegmentedValue<VAL>>>)> + 's {
+        self.category_keys.iter().map(move |key| {
+            (
+                key,
+                self.series
+                    .iter()
+                    .map(|(_, values)| values.category(key))
+                    .collect(),
+            )
+        })
+    }
+}
+
+#[test]
+fn aligned_iteration_with_missing_category() {
+    let bars = CategorisedValues::new().add_data(vec![(1977, 36), (1978, 61), (1979, 78)]);
+    let line = CategorisedValues::new().add_data(vec![(1979, 102), (1977, 127)]);
+
+    let series = SeriesSet::new()
+        .with_series("bars", bars)
+        .with_series("line", line);
+
+    assert_eq!(series.series_names().collect::<Vec<&str>>(), vec!["bars", "line"]);
+
+    let heights = series
+        .aligned()
+        .map(|(year, values)| {
+            (
+                *year,
+                values
+                    .iter()
+                    .map(|value| value.map(|segmented| segmented.height()))
+                    .collect::<Vec<Option<i32>>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        heights,
+        vec![
+            (1977, vec![Some(36), Some(127)]),
+            (1978, vec![Some(61), None]),
+            (1979, vec![Some(78), Some(102)]),
+        ]
+    );
+}
+
+#[test]
+fn predefined_category_order() {
+    let series = SeriesSet::new()
+        .with_series("a", CategorisedValues::new().add_data(vec![("B", 1), ("C", 2)]))
+        .with_categories(vec!["C", "A"])
+        .with_series("b", CategorisedValues::new().add_data(vec![("D", 3)]));
+
+    let categories = series.aligned().map(|(category, _)| *category).collect::<Vec<&str>>();
+
+    assert_eq!(categories, vec!["C", "A", "B", "D"]);
+    assert_eq!(series.series("b").map(|values| values.category(&"D").unwrap().height()), Some(3));
+}
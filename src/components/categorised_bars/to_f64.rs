@@ -0,0 +1,20 @@
+/// Convert a value of a collection to a number for the scales and the arithmetic of the
+/// charts, for every primitive number, including those that do not fit an f64 losslessly,
+/// e.g. the `usize` counts of `add_data`.
+pub trait ToF64: Copy {
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($number:ty),*) => {
+        $(
+            impl ToF64 for $number {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
@@ -76,6 +76,41 @@ fn bar_width() {
     assert_eq!(group_bar_width, 100)
 }
 
+#[test]
+fn bar_width_of_the_layout() {
+    let labels = (1967..=1974).map(|y| y.into()).collect::<Vec<BarLabel>>();
+    let group = BarGroup::new("years").define_labels(labels);
+
+    let layout = group.bar_positions(800);
+    assert_eq!(layout.bar_width(), group.calculate_bar_width(800));
+    assert_eq!(layout.bar_width(), 100);
+    assert_eq!(group.bar_positions_fixed(40).bar_width(), 40);
+
+    // The leftover pixels widen the first bars beyond the width of the layout.
+    let layout = group.bar_positions(803);
+    assert_eq!(layout.bar_width(), 100);
+    let widths = layout.map(|bar| bar.width()).collect::<Vec<usize>>();
+    assert_eq!(widths, vec![101, 101, 101, 100, 100, 100, 100, 100]);
+}
+
+#[test]
+fn leftover_pixels_widen_first_bars() {
+    let labels = (1967..=1973).map(|y| y.into()).collect::<Vec<BarLabel>>();
+    let group = BarGroup::new("years").define_labels(labels);
+    assert_eq!(group.calculate_bar_width(800), 114);
+
+    let widths = group
+        .bar_positions(800)
+        .map(|bar| bar.position_end + 1 - bar.position_start)
+        .collect::<Vec<_>>();
+    assert_eq!(widths, vec![115, 115, 114, 114, 114, 114, 114]);
+    assert_eq!(group.bar_positions(800).last().unwrap().position_end, 800);
+
+    let group = sixties_and_seventies();
+    let last = group.bar_positions(119).last().unwrap();
+    assert_eq!(last.position_end + 10 + 7, 119);
+}
+
 #[cfg(test)]
 fn sixties_and_seventies() -> BarGroup {
     let labels_60 = (1967..1970).map(|y| y.into()).collect::<Vec<BarLabel>>();
@@ -112,6 +147,28 @@ fn bar_width_with_margins() {
     assert_eq!(group_bar_width, 4);
 }
 
+#[test]
+fn margins_fit_in_dimension() {
+    let group = sixties_and_seventies();
+
+    assert!(!group.margins_fit(10));
+    assert!(!group.margins_fit(84));
+    assert!(group.margins_fit(85));
+    assert!(group.margins_fit(884));
+}
+
+#[test]
+fn bar_width_for_total_width() {
+    let group = sixties_and_seventies();
+
+    assert_eq!(group.bar_width_for_total(884), 100);
+    assert_eq!(group.bar_width_for_total(116), 4);
+    assert_eq!(group.width_for_bar_width(group.bar_width_for_total(903)), 900);
+    assert_eq!(group.bar_width_for_total(50), 0);
+
+    assert_eq!(BarGroup::new("empty").bar_width_for_total(100), 0);
+}
+
 #[test]
 fn labels_with_dimensions() {
     let group = sixties_and_seventies();
@@ -148,6 +205,33 @@ fn labels_with_dimensions() {
     assert_eq!(result, String::from("+++++**1967***1968***1969****++++++********1970*********1971*********1972*********1973*********1974**********+++++++").replace("+", "*"));
 }
 
+#[test]
+fn labels_with_dimensions_right_to_left() {
+    let group = sixties_and_seventies();
+
+    let mut bars = group.bar_positions_rev(116).collect::<Vec<_>>();
+    assert_eq!(bars[0].key, 1967);
+    assert_eq!((bars[0].position_start, bars[0].position_end), (106, 109));
+    assert!(bars.iter().all(|bar| bar.position_start < bar.position_end));
+
+    bars.sort_by_key(|bar| bar.position_start);
+    let mut position = 1;
+    let mut result = String::new();
+    for bar in bars {
+        let star_count = bar.position_start - position;
+        let stars = repeat("*").take(star_count).collect::<String>();
+        result.push_str(stars.as_str());
+        let label = format!("{}", bar.key);
+        result.push_str(label.as_str());
+        position = bar.position_end + 1;
+    }
+    let star_count = 117 - position;
+    let stars = repeat("*").take(star_count).collect::<String>();
+    result.push_str(stars.as_str());
+
+    assert_eq!(result, String::from("+++++++**********1974*********1973*********1972*********1971*********1970********++++++****1969***1968***1967**+++++").replace("+", "*"));
+}
+
 #[test]
 fn group_labels_with_dimensions() {
     let group = sixties_and_seventies();
@@ -165,3 +249,85 @@ fn group_labels_with_dimensions() {
 
     assert_eq!(group.width_for_bar_width(bar_width), 116);
 }
+
+#[test]
+fn bar_positions_at_fixed_bar_width() {
+    let group = sixties_and_seventies();
+
+    let fitted = group
+        .bar_positions(116)
+        .map(|bar| (bar.key, bar.position_start, bar.position_end))
+        .collect::<Vec<_>>();
+    let fixed = group
+        .bar_positions_fixed(group.calculate_bar_width(116))
+        .map(|bar| (bar.key, bar.position_start, bar.position_end))
+        .collect::<Vec<_>>();
+    assert_eq!(fixed, fitted);
+
+    let last = group.bar_positions_fixed(20).last().unwrap();
+    let trailing_margins = 10 + 7;
+    assert_eq!(last.key, 1974);
+    assert_eq!(last.position_end + trailing_margins, group.width_for_bar_width(20));
+}
+
+#[test]
+fn empty_group_margins() {
+    let group = BarGroup::new("empty").with_margins(2, 3, 4);
+
+    assert_eq!(group.child_count(), 0);
+    assert_eq!(group.margin_total(), 6);
+    assert_eq!(group.width_for_bar_width(20), 6);
+}
+
+#[test]
+fn separators_between_groups() {
+    let group = sixties_and_seventies();
+    assert!(group.separator_positions(116).is_empty());
+
+    let group = group.with_group_separators(true);
+    let separators = group.separator_positions(116);
+    assert_eq!(separators, vec![34]);
+
+    let bars = group.bar_positions(116).collect::<Vec<_>>();
+    let last_sixties = bars.iter().find(|bar| bar.key == 1969).unwrap();
+    let first_seventies = bars.iter().find(|bar| bar.key == 1970).unwrap();
+    assert_eq!((last_sixties.position_end, first_seventies.position_start), (25, 44));
+
+    let svg = group.separators_to_svg(116, 300).to_string();
+    assert_eq!(svg.matches("class=\"group-separator\"").count(), 1);
+    assert!(svg.contains("x1=\"34\" x2=\"34\" y1=\"0\" y2=\"300\""));
+
+    // The leftover pixels widen the first bars and shift the separator with them.
+    let separators = group.separator_positions(119);
+    let bars = group.bar_positions(119).collect::<Vec<_>>();
+    let last_sixties = bars.iter().find(|bar| bar.key == 1969).unwrap();
+    let first_seventies = bars.iter().find(|bar| bar.key == 1970).unwrap();
+    assert_eq!((last_sixties.position_end, first_seventies.position_start), (28, 47));
+    assert_eq!(separators, vec![37]);
+}
+
+#[test]
+fn label_margins_shift_later_bars() {
+    let mut labels = (1967..=1970).map(|y| y.into()).collect::<Vec<BarLabel>>();
+    labels.push(BarLabel::from((0, "total")).with_margin_before(30).with_margin_after(5));
+    let group = BarGroup::new("years").with_margin_between(2).define_labels(labels);
+
+    assert_eq!(group.margin_total(), 4 * 2 + 30 + 5);
+    assert_eq!(group.calculate_bar_width(143), 20);
+
+    let positions = group
+        .bar_positions_fixed(20)
+        .map(|bar| (bar.key, bar.position_start, bar.position_end))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        positions,
+        vec![
+            (1967, 1, 20),
+            (1968, 23, 42),
+            (1969, 45, 64),
+            (1970, 67, 86),
+            (0, 119, 138)
+        ]
+    );
+    assert_eq!(group.width_for_bar_width(20), 143);
+}
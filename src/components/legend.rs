@@ -1,14 +1,18 @@
-use svg::node::element::{Group, Circle, Rectangle, Line};
+use svg::node::element::{Group, Circle, Rectangle, Line, Path, Polygon};
 use svg::Node;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
+use format_num::NumberFormat;
 use crate::MarkerType;
+use crate::ChartError;
 
 /// Represents the possible marker types that a legend entry can have.
 pub enum LegendMarkerType {
     Circle,
     Square,
     X,
+    Triangle,
+    Diamond,
     Line,
 }
 
@@ -18,10 +22,47 @@ impl From<MarkerType> for LegendMarkerType {
             MarkerType::Circle => LegendMarkerType::Circle,
             MarkerType::Square => LegendMarkerType::Square,
             MarkerType::X => LegendMarkerType::X,
+            MarkerType::Triangle => LegendMarkerType::Triangle,
+            MarkerType::Diamond => LegendMarkerType::Diamond,
         }
     }
 }
 
+/// Represents the value that is appended to the label of each legend entry.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LegendValueMode {
+    None,
+    Absolute,
+    Percentage,
+}
+
+impl LegendValueMode {
+    /// Append the value, or its share of the total, to the label, e.g. "CD (942.5M)" or "CD (43%)".
+    pub(crate) fn append_to(&self, label: &str, value: f32, total: f32) -> String {
+        match self {
+            LegendValueMode::None => label.to_owned(),
+            LegendValueMode::Absolute => format!("{} ({})", label, Self::format_si(value)),
+            LegendValueMode::Percentage => {
+                let share = if total == 0_f32 { 0_f32 } else { value / total * 100_f32 };
+                format!("{} ({}%)", label, share.round())
+            }
+        }
+    }
+
+    /// Format the value to four significant digits with an SI suffix, without trailing zeros.
+    fn format_si(value: f32) -> String {
+        let formatted = NumberFormat::new().format(".4s", value);
+        let (number, suffix) = formatted.split_at(formatted.find(char::is_alphabetic).unwrap_or(formatted.len()));
+        let number = if number.contains('.') {
+            number.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            number
+        };
+
+        format!("{}{}", number, suffix.replace('G', "B"))
+    }
+}
+
 /// Represents an entry in the chart's legend.
 pub struct LegendEntry {
     marker_type: LegendMarkerType,
@@ -30,6 +71,7 @@ pub struct LegendEntry {
     color: String,
     stroke_type: String,
     label: String,
+    swatch_border: Option<String>,
 }
 
 impl LegendEntry {
@@ -42,9 +84,20 @@ impl LegendEntry {
             color,
             stroke_type,
             label,
+            swatch_border: None,
         }
     }
 
+    /// Return the color of the legend entry marker.
+    pub fn get_color(&self) -> &str {
+        &self.color
+    }
+
+    /// Set the color of the border drawn around circle and square markers.
+    pub fn set_swatch_border(&mut self, border_color: Option<String>) {
+        self.swatch_border = border_color;
+    }
+
     /// Return legend entry width to compute the placement of legend entries on the chart.
     pub fn get_width(&self) -> usize {
         // TODO ideally, compute the length of the given `label` in the given font and size
@@ -52,7 +105,8 @@ impl LegendEntry {
         avg_letter_width * self.label.len() + self.marker_size * 2 + self.marker_to_label_gap
     }
 
-    pub fn to_svg(&self) -> Result<Group, String> {
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        let swatch_stroke = self.swatch_border.as_deref().unwrap_or("none");
         let mut group = Group::new()
             .set("class", "legend-entry");
 
@@ -63,7 +117,7 @@ impl LegendEntry {
                     .set("cy", self.marker_size)
                     .set("r", self.marker_size)
                     .set("fill", self.color.as_ref())
-                    .set("stroke", "none")
+                    .set("stroke", swatch_stroke)
             ),
             LegendMarkerType::Square => group.append(
                 Rectangle::new()
@@ -72,7 +126,7 @@ impl LegendEntry {
                     .set("width", 2 * self.marker_size)
                     .set("height", 2 * self.marker_size)
                     .set("fill", self.color.as_ref())
-                    .set("stroke", "none")
+                    .set("stroke", swatch_stroke)
             ),
             LegendMarkerType::X => {
                 group.append(
@@ -94,6 +148,18 @@ impl LegendEntry {
                         .set("stroke-width", "2px")
                 )
             },
+            LegendMarkerType::Triangle => group.append(
+                Polygon::new()
+                    .set("points", format!("{},0 {},{} 0,{}", self.marker_size, 2 * self.marker_size, 2 * self.marker_size, 2 * self.marker_size))
+                    .set("fill", self.color.as_ref())
+                    .set("stroke", swatch_stroke)
+            ),
+            LegendMarkerType::Diamond => group.append(
+                Path::new()
+                    .set("d", format!("M{},0 L{},{} L{},{} L0,{} Z", self.marker_size, 2 * self.marker_size, self.marker_size, self.marker_size, 2 * self.marker_size, self.marker_size))
+                    .set("fill", self.color.as_ref())
+                    .set("stroke", swatch_stroke)
+            ),
             LegendMarkerType::Line => group.append(
                 Line::new()
                     .set("x1", 0)
@@ -1,15 +1,20 @@
 use std::fmt::Display;
 use svg::node::element::{Group, Path};
-use svg::node::element::path::Data;
 use svg::node::Node;
 use crate::components::DatumRepresentation;
+use crate::ChartError;
+use crate::components::area::Curve;
 use crate::components::scatter::ScatterPoint;
 
+/// The way in which a line connects its points, which is shared with the top of an area.
+pub type LineInterpolation = Curve;
+
 /// Represents a point in a scatter plot.
 #[derive(Debug)]
 pub struct LineSeries<T: Display, U: Display> {
     points: Vec<ScatterPoint<T, U>>,
     color: String,
+    curve: Curve,
 }
 
 impl<T: Display, U: Display> LineSeries<T, U> {
@@ -20,25 +25,35 @@ impl<T: Display, U: Display> LineSeries<T, U> {
         Self {
             points,
             color,
+            curve: Curve::Linear,
         }
     }
+
+    /// Set the way in which the line connects its points.
+    pub fn set_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Set the interpolation between the points of the line, e.g. `LineInterpolation::CatmullRom`
+    /// with a tension from 0 to 1 for a smooth curve.
+    pub fn with_interpolation(self, interpolation: LineInterpolation) -> Self {
+        self.set_curve(interpolation)
+    }
 }
 
 impl<T: Display, U: Display> DatumRepresentation for LineSeries<T, U> {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        if self.points.is_empty() {
+            return Err(ChartError::EmptyData);
+        }
+
         let mut group = Group::new()
             .set("class", "line");
 
-        let mut data = Data::new();
-
-        for (i, point) in self.points.iter().enumerate() {
-            if i == 0 {
-                data = data.move_to((point.get_x(), point.get_y()));
-            } else {
-                data = data.line_to((point.get_x(), point.get_y()));
-            }
-        }
+        let coordinates = self.points.iter().map(|point| (point.get_x(), point.get_y())).collect::<Vec<(f32, f32)>>();
+        let data = self.curve.draw(&coordinates);
 
         let line = Path::new()
             .set("fill", "none")
@@ -55,3 +70,48 @@ impl<T: Display, U: Display> DatumRepresentation for LineSeries<T, U> {
         Ok(group)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::scatter::{MarkerType, PointLabelPosition};
+
+    fn line_path(curve: Curve) -> String {
+        let points = vec![(0_f32, 120_f32), (60_f32, 60_f32), (120_f32, 120_f32)]
+            .into_iter()
+            .map(|(x, y)| ScatterPoint::new(x, y, MarkerType::Circle, 5, x, y, PointLabelPosition::NW, false, false, String::from("#fff")))
+            .collect::<Vec<ScatterPoint<f32, f32>>>();
+        let svg = LineSeries::new(points, String::from("#1f77b4")).set_curve(curve).to_svg().unwrap().to_string();
+
+        svg.split(" d=\"").nth(1).unwrap().split('"').next().unwrap().to_owned()
+    }
+
+    #[test]
+    fn linear_line_is_a_polyline() {
+        assert_eq!(line_path(Curve::Linear), "M0,120 L60,60 L120,120");
+    }
+
+    #[test]
+    fn catmull_rom_line_is_a_smooth_curve() {
+        assert_eq!(line_path(Curve::CatmullRom(0_f32)), "M0,120 C10,110,40,60,60,60 C80,60,110,110,120,120");
+        assert_eq!(line_path(Curve::CatmullRom(1_f32)), "M0,120 C0,120,60,60,60,60 C60,60,120,120,120,120");
+    }
+
+    #[test]
+    fn interpolation_sets_the_curve() {
+        let points = vec![(0_f32, 120_f32), (60_f32, 60_f32), (120_f32, 120_f32)]
+            .into_iter()
+            .map(|(x, y)| ScatterPoint::new(x, y, MarkerType::Circle, 5, x, y, PointLabelPosition::NW, false, false, String::from("#fff")))
+            .collect::<Vec<ScatterPoint<f32, f32>>>();
+        let line = LineSeries::new(points, String::from("#1f77b4")).with_interpolation(LineInterpolation::CatmullRom(0.5_f32));
+        let svg = line.to_svg().unwrap().to_string();
+
+        assert!(svg.contains("d=\"M0,120 C5,115,50,60,60,60 C70,60,115,115,120,120\""));
+    }
+
+    #[test]
+    fn stepped_lines_hold_their_value() {
+        assert_eq!(line_path(Curve::StepBefore), "M0,120 L0,60 L60,60 L60,120 L120,120");
+        assert_eq!(line_path(Curve::StepAfter), "M0,120 L60,120 L60,60 L120,60 L120,120");
+    }
+}
@@ -1,16 +1,97 @@
+use std::cell::Cell;
 use svg::node::element::Group;
+use crate::ChartError;
 
+pub(crate) mod annotation;
 pub(crate) mod area;
 pub(crate) mod axis;
 pub(crate) mod bar;
+pub(crate) mod box_plot;
 pub(crate) mod categorised_bars;
 pub(crate) mod legend;
 pub(crate) mod line;
 mod ordered_set;
 pub(crate) use ordered_set::OrderedSet;
 pub(crate) mod scatter;
+pub(crate) mod stack;
+
+thread_local! {
+    /// The number of nested render scopes and the next id to hand out within the outermost one.
+    static RENDER_IDS: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+/// Generate an id for an element that can be referenced from other elements
+/// (e.g. clip paths or filters), making sure it does not clash with other ids of the same render.
+pub(crate) fn unique_id(prefix: &str) -> String {
+    let id = RENDER_IDS.with(|ids| {
+        let (depth, next) = ids.get();
+        ids.set((depth, next + 1));
+        next
+    });
+
+    format!("{}-{}", prefix, id)
+}
+
+/// The scope of one render, in which `unique_id` counts from zero, so that rendering the
+/// same chart twice gives the same SVG. Scopes entered within it, like those of the views
+/// of a chart, continue its count. The scope ends when the value is dropped.
+pub(crate) struct IdScope;
+
+impl IdScope {
+    pub(crate) fn enter() -> Self {
+        RENDER_IDS.with(|ids| {
+            let (depth, next) = ids.get();
+            ids.set((depth + 1, if depth == 0 { 0 } else { next }));
+        });
+
+        IdScope
+    }
+}
+
+impl Drop for IdScope {
+    fn drop(&mut self) {
+        RENDER_IDS.with(|ids| {
+            let (depth, next) = ids.get();
+            ids.set((depth - 1, next));
+        });
+    }
+}
 
 /// A trait that defines behavior of chart components.
 pub trait DatumRepresentation {
-    fn to_svg(&self) -> Result<Group, String>;
+    fn to_svg(&self) -> Result<Group, ChartError>;
+}
+
+/// A trait that defines how the rendered width of a text is measured.
+/// This allows layout decisions (e.g. rotating or thinning tick labels)
+/// without having access to the actual font metrics of the renderer.
+pub trait TextMeasurer {
+    /// Return the width in pixels that the text needs when rendered.
+    fn measure(&self, text: &str) -> f32;
+}
+
+/// A text measurer that assumes that every character has the same width.
+#[derive(Debug, Copy, Clone)]
+pub struct AverageCharWidthMeasurer {
+    char_width: f32,
+}
+
+impl AverageCharWidthMeasurer {
+    /// Create a new measurer with the given average width of a character.
+    pub fn new(char_width: f32) -> Self {
+        Self { char_width }
+    }
+}
+
+impl Default for AverageCharWidthMeasurer {
+    /// The default average width is for the sans-serif 12px font used in the axes + some buffer.
+    fn default() -> Self {
+        Self::new(7_f32)
+    }
+}
+
+impl TextMeasurer for AverageCharWidthMeasurer {
+    fn measure(&self, text: &str) -> f32 {
+        text.chars().count() as f32 * self.char_width
+    }
 }
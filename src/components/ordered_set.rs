@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash, ops::Index, slice::Iter};
+use std::{collections::HashMap, hash::Hash, iter::FromIterator, ops::Index, slice::Iter, vec::IntoIter};
 
 #[derive(Debug, Default)]
 pub struct OrderedSet<O>
@@ -26,19 +26,42 @@ where
         self.list.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn contains(&self, key: &O) -> bool {
+        self.map.contains_key(key)
+    }
+
     pub fn define_if_not_exist(&mut self, key: &O) -> usize {
         if let Some(index) = self.map.get(&key) {
             *index
         } else {
-            let item = (*key).clone();
             let index = self.list.len();
-            self.list.push(item.clone());
-            self.map.insert(item.clone(), index);
+            self.map.insert(key.clone(), index);
+            self.list.push(key.clone());
 
             index
         }
     }
 
+    /// Remove the key and return the index it had
+    ///
+    /// The keys after it move up one index to keep the indices contiguous,
+    /// so indices obtained before the removal no longer refer to the same keys.
+    pub fn remove(&mut self, key: &O) -> Option<usize> {
+        let removed_index = self.map.remove(key)?;
+        self.list.remove(removed_index);
+        for shifted in self.list[removed_index..].iter() {
+            if let Some(index) = self.map.get_mut(shifted) {
+                *index -= 1;
+            }
+        }
+
+        Some(removed_index)
+    }
+
     pub fn index_of(&self, key: &O) -> Option<usize> {
         if let Some(index_ref) = self.map.get(key) {
             Some(*index_ref)
@@ -71,6 +94,53 @@ where
     }
 }
 
+impl<O> FromIterator<O> for OrderedSet<O>
+where
+    O: Clone + Default + Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = O>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+
+        set
+    }
+}
+
+impl<O> Extend<O> for OrderedSet<O>
+where
+    O: Clone + Default + Hash + Eq,
+{
+    fn extend<I: IntoIterator<Item = O>>(&mut self, iter: I) {
+        for key in iter {
+            self.define_if_not_exist(&key);
+        }
+    }
+}
+
+impl<O> IntoIterator for OrderedSet<O>
+where
+    O: Default + Hash + Eq,
+{
+    type Item = O;
+    type IntoIter = IntoIter<O>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'s, O> IntoIterator for &'s OrderedSet<O>
+where
+    O: Default + Hash + Eq,
+{
+    type Item = &'s O;
+    type IntoIter = Iter<'s, O>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn ordered_numbers() {
@@ -180,3 +250,93 @@ fn iterate() {
     assert_eq!(iter.next(), Some(&"B"));
     assert_eq!(iter.next(), None);
 }
+
+#[cfg(test)]
+#[test]
+fn remove_middle_key() {
+    let mut indices: OrderedSet<&str> = OrderedSet::new();
+
+    for item in ["A", "B", "C", "D"].iter() {
+        indices.define_if_not_exist(item);
+    }
+
+    assert_eq!(indices.remove(&"B"), Some(1));
+    assert_eq!(indices.remove(&"B"), None);
+    assert_eq!(indices.len(), 3);
+
+    assert_eq!(indices.index_of(&"A"), Some(0));
+    assert_eq!(indices.index_of(&"B"), None);
+    assert_eq!(indices.index_of(&"C"), Some(1));
+    assert_eq!(indices.index_of(&"D"), Some(2));
+
+    assert_eq!(indices.key(1), Some(&"C"));
+    assert_eq!(indices.key(2), Some(&"D"));
+    assert_eq!(indices.key(3), None);
+
+    assert_eq!(indices.define_if_not_exist(&"B"), 3);
+}
+
+#[cfg(test)]
+#[test]
+fn collect_ordered_strs() {
+    let indices: OrderedSet<&str> = ["A", "C", "B", "C", "B", "D"].iter().cloned().collect();
+
+    assert_eq!(indices.len(), 4);
+    assert_eq!(indices.index_of(&"A"), Some(0));
+    assert_eq!(indices.index_of(&"B"), Some(2));
+    assert_eq!(indices.index_of(&"C"), Some(1));
+    assert_eq!(indices.index_of(&"D"), Some(3));
+    assert_eq!(indices.index_of(&"E"), None);
+
+    assert_eq!(indices.key(0), Some(&"A"));
+    assert_eq!(indices.key(1), Some(&"C"));
+    assert_eq!(indices.key(2), Some(&"B"));
+    assert_eq!(indices.key(3), Some(&"D"));
+    assert_eq!(indices.key(4), None);
+}
+
+#[cfg(test)]
+#[test]
+fn extend_keeps_first_seen_order() {
+    let mut indices: OrderedSet<u8> = vec![17, 12, 99].into_iter().collect();
+
+    indices.extend(vec![12, 1, 17, 5]);
+
+    assert_eq!(indices.len(), 5);
+    assert_eq!(indices.index_of(&17), Some(0));
+    assert_eq!(indices.index_of(&12), Some(1));
+    assert_eq!(indices.index_of(&99), Some(2));
+    assert_eq!(indices.index_of(&1), Some(3));
+    assert_eq!(indices.index_of(&5), Some(4));
+}
+
+#[cfg(test)]
+#[test]
+fn iterate_in_for_loop() {
+    let indices: OrderedSet<&str> = ["C", "D", "A", "B", "C", "B"].iter().cloned().collect();
+
+    let mut visited = Vec::new();
+    for key in &indices {
+        visited.push(*key);
+    }
+    assert_eq!(visited, vec!["C", "D", "A", "B"]);
+
+    assert_eq!(indices.into_iter().collect::<Vec<&str>>(), vec!["C", "D", "A", "B"]);
+}
+
+#[cfg(test)]
+#[test]
+fn membership_and_emptiness() {
+    let mut indices: OrderedSet<&str> = OrderedSet::new();
+    assert!(indices.is_empty());
+    assert!(!indices.contains(&"A"));
+
+    indices.define_if_not_exist(&"A");
+    assert!(!indices.is_empty());
+    assert!(indices.contains(&"A"));
+    assert!(!indices.contains(&"B"));
+
+    indices.remove(&"A");
+    assert!(indices.is_empty());
+    assert!(!indices.contains(&"A"));
+}
@@ -1,9 +1,10 @@
 use std::fmt::Display;
-use svg::node::element::{Group, Circle, Rectangle, Line};
+use svg::node::element::{Group, Circle, Rectangle, Line, Path, Polygon, Title};
 use svg::node::Node;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
-use crate::components::DatumRepresentation;
+use crate::components::{DatumRepresentation, TextMeasurer};
+use crate::ChartError;
 
 /// Define the possible types of points in a scatter plot.
 #[derive(Debug, Copy, Clone)]
@@ -11,6 +12,8 @@ pub enum MarkerType {
     Circle,
     Square,
     X,
+    Triangle,
+    Diamond,
 }
 
 /// Define the possible locations of a point's label.
@@ -31,13 +34,18 @@ pub enum PointLabelPosition {
 pub struct ScatterPoint<T: Display, U: Display> {
     label_position: PointLabelPosition,
     label_visible: bool,
+    label_text: Option<String>,
     point_visible: bool,
+    tooltip_visible: bool,
     marker_type: MarkerType,
     marker_size: usize,
     x: f32,
     y: f32,
     x_label: T,
     y_label: U,
+    size: Option<f32>,
+    /// The coordinates as formatted by the scales of the view, to list in the tooltip.
+    formatted_coordinates: Option<(String, String, Option<String>)>,
     color: String,
 }
 
@@ -57,17 +65,58 @@ impl<T: Display, U: Display> ScatterPoint<T, U> {
         Self {
             label_position,
             label_visible,
+            label_text: None,
             point_visible,
+            tooltip_visible: false,
             marker_type,
             marker_size,
             x,
             y,
             x_label,
             y_label,
+            size: None,
+            formatted_coordinates: None,
             color,
         }
     }
 
+    /// Set whether the point should have a tooltip listing its coordinates.
+    pub fn set_tooltip_visibility(mut self, tooltip_visibility: bool) -> Self {
+        self.tooltip_visible = tooltip_visibility;
+        self
+    }
+
+    /// Set the size of the datum that the point represents, e.g. in a bubble chart.
+    pub fn set_size(mut self, size: Option<f32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Return the size of the datum that the point represents, if any.
+    pub fn get_size(&self) -> Option<f32> {
+        self.size
+    }
+
+    /// Set the x, y and size (if any) of the point as formatted by the scales, to list in the tooltip
+    /// instead of their `Display` representation.
+    pub fn set_formatted_coordinates(mut self, x: String, y: String, size: Option<String>) -> Self {
+        self.formatted_coordinates = Some((x, y, size));
+        self
+    }
+
+    /// Return the text of the tooltip that lists all the coordinates of the point.
+    pub fn get_tooltip(&self) -> String {
+        let (x, y, size) = match &self.formatted_coordinates {
+            Some((x, y, size)) => (x.clone(), y.clone(), size.clone()),
+            None => (self.x_label.to_string(), self.y_label.to_string(), None),
+        };
+
+        match size.or_else(|| self.size.map(|size| size.to_string())) {
+            Some(size) => format!("x: {}, y: {}, size: {}", x, y, size),
+            None => format!("x: {}, y: {}", x, y),
+        }
+    }
+
     /// Return the x coordinate of the point.
     pub fn get_x(&self) -> f32 {
         self.x
@@ -77,14 +126,84 @@ impl<T: Display, U: Display> ScatterPoint<T, U> {
     pub fn get_y(&self) -> f32 {
         self.y
     }
+
+    /// Set the text of the label, instead of the coordinates of the point.
+    pub fn set_label_text(mut self, label_text: Option<String>) -> Self {
+        self.label_text = label_text;
+        self
+    }
+
+    /// Set whether the label of the point should be displayed.
+    pub fn set_label_visibility(mut self, label_visibility: bool) -> Self {
+        self.label_visible = label_visibility;
+        self
+    }
+
+    /// Return whether the label of the point is displayed.
+    pub fn is_label_visible(&self) -> bool {
+        self.label_visible
+    }
+
+    /// Return the text of the label, which defaults to the coordinates of the point.
+    pub fn get_label_text(&self) -> String {
+        match &self.label_text {
+            Some(label_text) => label_text.clone(),
+            None => format!("({}, {})", self.x_label, self.y_label),
+        }
+    }
+
+    /// Return the bounding box `(left, top, right, bottom)` of the label on the chart,
+    /// assuming a 14px high text that is measured by the given measurer.
+    pub fn label_bounds(&self, measurer: &dyn TextMeasurer) -> (f32, f32, f32, f32) {
+        let (x, y, text_anchor) = self.label_placement();
+        let width = measurer.measure(&self.get_label_text());
+        let left = match text_anchor {
+            "start" => x as f32,
+            "middle" => x as f32 - width / 2_f32,
+            _ => x as f32 - width,
+        };
+
+        (self.x + left, self.y + y as f32 - 7_f32, self.x + left + width, self.y + y as f32 + 7_f32)
+    }
+
+    /// Return the position of the label relative to the point and its text anchor,
+    /// keeping the label clear of the marker.
+    fn label_placement(&self) -> (isize, isize, &'static str) {
+        let label_offset = self.marker_size as isize;
+        match self.label_position {
+            PointLabelPosition::N => (0, -label_offset - 12, "middle"),
+            PointLabelPosition::NE => (label_offset + 4, -label_offset - 8, "start"),
+            PointLabelPosition::E => (label_offset + 8, 0, "start"),
+            PointLabelPosition::SE => (label_offset + 4, label_offset + 8, "start"),
+            PointLabelPosition::S => (0, label_offset + 12, "middle"),
+            PointLabelPosition::SW => (-label_offset - 4, label_offset + 8, "end"),
+            PointLabelPosition::W => (-label_offset - 8, 0, "end"),
+            PointLabelPosition::NW => (-label_offset - 4, -label_offset - 8, "end"),
+        }
+    }
+
+    /// Return the x value of the datum that the point represents.
+    pub fn get_x_label(&self) -> &T {
+        &self.x_label
+    }
+
+    /// Return the y value of the datum that the point represents.
+    pub fn get_y_label(&self) -> &U {
+        &self.y_label
+    }
 }
 
 impl<T: Display, U: Display> DatumRepresentation for ScatterPoint<T, U> {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new()
             .set("transform", format!("translate({},{})", self.x, self.y))
-            .set("class", "scatter-point");
+            .set("class", "scatter-point")
+            .set("aria-label", self.get_tooltip());
+
+        if self.tooltip_visible {
+            group.append(Title::new().add(TextNode::new(self.get_tooltip())));
+        }
 
         match self.marker_type {
             MarkerType::Circle if self.point_visible => {
@@ -129,63 +248,61 @@ impl<T: Display, U: Display> DatumRepresentation for ScatterPoint<T, U> {
                         )
                 );
             },
+            MarkerType::Triangle if self.point_visible => {
+                let size = self.marker_size as i32;
+                group.append(
+                    Polygon::new()
+                        .set("points", format!("0,{} {},{} {},{}", -size, size, size, -size, size))
+                        .set("fill", self.color.as_ref())
+                );
+            },
+            MarkerType::Diamond if self.point_visible => {
+                let size = self.marker_size as i32;
+                group.append(
+                    Path::new()
+                        .set("d", format!("M0,{} L{},0 L0,{} L{},0 Z", -size, size, size, -size))
+                        .set("fill", self.color.as_ref())
+                );
+            },
             _ => {},
         };
 
         if self.label_visible {
-            let mut point_label = Text::new()
+            let (x, y, text_anchor) = self.label_placement();
+            let point_label = Text::new()
+                .set("x", x)
+                .set("y", y)
+                .set("text-anchor", text_anchor)
                 .set("dy", ".35em")
                 .set("font-family", "sans-serif")
                 .set("fill", "#333")
                 .set("font-size", "14px")
-                .add(TextNode::new(format!("({}, {})", self.x_label, self.y_label)));
-
-            let label_offset = self.marker_size as isize;
-            match self.label_position {
-                PointLabelPosition::N => {
-                    point_label.assign("x", 0);
-                    point_label.assign("y", -label_offset - 12);
-                    point_label.assign("text-anchor", "middle");
-                },
-                PointLabelPosition::NE => {
-                    point_label.assign("x", label_offset + 4);
-                    point_label.assign("y", -label_offset - 8);
-                    point_label.assign("text-anchor", "start");
-                },
-                PointLabelPosition::E => {
-                    point_label.assign("x", label_offset + 8);
-                    point_label.assign("y", 0);
-                    point_label.assign("text-anchor", "start");
-                },
-                PointLabelPosition::SE => {
-                    point_label.assign("x", label_offset + 4);
-                    point_label.assign("y", label_offset + 8);
-                    point_label.assign("text-anchor", "start");
-                },
-                PointLabelPosition::S => {
-                    point_label.assign("x", 0);
-                    point_label.assign("y", label_offset + 12);
-                    point_label.assign("text-anchor", "middle");
-                },
-                PointLabelPosition::SW => {
-                    point_label.assign("x", -label_offset - 4);
-                    point_label.assign("y", label_offset + 8);
-                    point_label.assign("text-anchor", "end");
-                },
-                PointLabelPosition::W => {
-                    point_label.assign("x", -label_offset - 8);
-                    point_label.assign("y", 0);
-                    point_label.assign("text-anchor", "end");
-                },
-                PointLabelPosition::NW => {
-                    point_label.assign("x", -label_offset - 4);
-                    point_label.assign("y", -label_offset - 8);
-                    point_label.assign("text-anchor", "end");
-                },
-            }
+                .add(TextNode::new(self.get_label_text()));
+
             group.append(point_label);
         }
 
         Ok(group)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker_svg(marker_type: MarkerType, marker_size: usize) -> String {
+        ScatterPoint::new(10_f32, 20_f32, marker_type, marker_size, 1, 2, PointLabelPosition::NW, false, true, String::from("#1f77b4"))
+            .to_svg()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn marker_element_per_type() {
+        assert!(marker_svg(MarkerType::Circle, 5).contains("<circle cx=\"0\" cy=\"0\" fill=\"#1f77b4\" r=\"5\"/>"));
+        assert!(marker_svg(MarkerType::Square, 5).contains("<rect fill=\"#1f77b4\" height=\"10\" width=\"10\" x=\"-5\" y=\"-5\"/>"));
+        assert!(marker_svg(MarkerType::Triangle, 5).contains("<polygon fill=\"#1f77b4\" points=\"0,-5 5,5 -5,5\"/>"));
+        assert!(marker_svg(MarkerType::Diamond, 5).contains("<path d=\"M0,-5 L5,0 L0,5 L-5,0 Z\" fill=\"#1f77b4\"/>"));
+        assert!(marker_svg(MarkerType::Diamond, 8).contains("d=\"M0,-8 L8,0 L0,8 L-8,0 Z\""));
+    }
+}
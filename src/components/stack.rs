@@ -0,0 +1,139 @@
+/// Define the algorithms that compute the baseline of stacked layers,
+/// as used in stacked area charts and streamgraphs.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StackOffset {
+    /// Stack the layers on top of a zero baseline.
+    Zero,
+    /// Center the stack around zero.
+    Silhouette,
+    /// Minimize the weighted wiggle (the sum of squared slopes) of the layers,
+    /// as described by Byron & Wattenberg in "Stacked Graphs – Geometry & Aesthetics".
+    Wiggle,
+}
+
+impl StackOffset {
+    /// Compute the baseline of the stack for every position, where `layers[i][j]`
+    /// is the value of layer `i` at position `j`. All layers must have the same length.
+    pub fn baseline(&self, layers: &[Vec<f32>]) -> Vec<f32> {
+        let positions = layers.first().map_or(0, |layer| layer.len());
+
+        match self {
+            StackOffset::Zero => vec![0_f32; positions],
+            StackOffset::Silhouette => (0..positions)
+                .map(|j| -layers.iter().map(|layer| layer[j]).sum::<f32>() / 2_f32)
+                .collect(),
+            StackOffset::Wiggle => {
+                let mut baseline = Vec::with_capacity(positions);
+                let mut y = 0_f32;
+
+                for j in 0..positions {
+                    if j > 0 {
+                        let mut total = 0_f32;
+                        let mut weighted_slope = 0_f32;
+                        let mut slope_below = 0_f32;
+
+                        for layer in layers.iter() {
+                            let delta = layer[j] - layer[j - 1];
+                            total += layer[j];
+                            weighted_slope += (slope_below + delta / 2_f32) * layer[j];
+                            slope_below += delta;
+                        }
+
+                        if total != 0_f32 {
+                            y -= weighted_slope / total;
+                        }
+                    }
+                    baseline.push(y);
+                }
+
+                baseline
+            }
+        }
+    }
+
+    /// Stack the layers on the computed baseline and return, for every layer and
+    /// position, the lower and upper boundaries of the layer.
+    pub fn stack(&self, layers: &[Vec<f32>]) -> Vec<Vec<(f32, f32)>> {
+        let mut lower = self.baseline(layers);
+
+        layers
+            .iter()
+            .map(|layer| {
+                layer
+                    .iter()
+                    .zip(lower.iter_mut())
+                    .map(|(value, lower)| {
+                        let boundaries = (*lower, *lower + value);
+                        *lower += value;
+                        boundaries
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers() -> Vec<Vec<f32>> {
+        vec![
+            vec![1_f32, 2_f32, 6_f32, 2_f32, 1_f32],
+            vec![4_f32, 3_f32, 1_f32, 3_f32, 5_f32],
+            vec![2_f32, 6_f32, 2_f32, 1_f32, 4_f32],
+        ]
+    }
+
+    /// The sum of the squared slopes of the middle of each layer, weighted by the layer's thickness.
+    fn weighted_wiggle(stacked: &[Vec<(f32, f32)>]) -> f32 {
+        stacked
+            .iter()
+            .map(|layer| {
+                layer
+                    .windows(2)
+                    .map(|pair| {
+                        let slope = (pair[1].0 + pair[1].1) / 2_f32 - (pair[0].0 + pair[0].1) / 2_f32;
+                        (pair[1].1 - pair[1].0) * slope * slope
+                    })
+                    .sum::<f32>()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn zero_and_silhouette_baselines() {
+        assert_eq!(StackOffset::Zero.baseline(&layers()), vec![0_f32; 5]);
+        assert_eq!(
+            StackOffset::Silhouette.baseline(&layers()),
+            vec![-3.5, -5.5, -4.5, -3_f32, -5_f32]
+        );
+    }
+
+    #[test]
+    fn stack_boundaries() {
+        let stacked = StackOffset::Zero.stack(&layers());
+
+        assert_eq!(stacked[0][1], (0_f32, 2_f32));
+        assert_eq!(stacked[1][1], (2_f32, 5_f32));
+        assert_eq!(stacked[2][1], (5_f32, 11_f32));
+    }
+
+    #[test]
+    fn wiggle_is_smoother_than_silhouette() {
+        let wiggle = StackOffset::Wiggle.baseline(&layers());
+        let silhouette = StackOffset::Silhouette.baseline(&layers());
+
+        assert_eq!(wiggle[0], 0_f32);
+        assert_ne!(wiggle, silhouette);
+        assert!(
+            weighted_wiggle(&StackOffset::Wiggle.stack(&layers()))
+                < weighted_wiggle(&StackOffset::Silhouette.stack(&layers()))
+        );
+    }
+
+    #[test]
+    fn no_layers() {
+        assert!(StackOffset::Wiggle.baseline(&[]).is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use std::error::Error;
+use std::fmt;
+
+/// The reasons why a chart component can not be rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartError {
+    /// There is no data to represent.
+    EmptyData,
+    /// The scale does not suit the data, e.g. a linear scale where a band scale is required.
+    InvalidScale(String),
+    /// A dimension that is needed to lay out the component is not set, e.g. the scale of an axis.
+    MissingDimension(String),
+    /// The options of a component contradict each other.
+    InvalidOption(String),
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartError::EmptyData => write!(f, "There is no data to represent."),
+            ChartError::InvalidScale(reason) => write!(f, "Invalid scale: {}", reason),
+            ChartError::MissingDimension(dimension) => write!(f, "The {} is not set.", dimension),
+            ChartError::InvalidOption(reason) => write!(f, "Invalid option: {}", reason),
+        }
+    }
+}
+
+impl Error for ChartError {}
+
+/// Keep code that reports errors as text working, e.g. a function that returns `Result<_, String>`
+/// and loads the data of a view with `?`.
+impl From<ChartError> for String {
+    fn from(error: ChartError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_convert_to_their_message() {
+        assert_eq!(String::from(ChartError::EmptyData), "There is no data to represent.");
+        assert_eq!(ChartError::InvalidScale(String::from("the X scale should be a band scale")).to_string(), "Invalid scale: the X scale should be a band scale");
+        assert_eq!(ChartError::MissingDimension(String::from("scale of the X dimension")).to_string(), "The scale of the X dimension is not set.");
+        assert_eq!(ChartError::InvalidOption(String::from("total labels need stacked bars")).to_string(), "Invalid option: total labels need stacked bars");
+    }
+}
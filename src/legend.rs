@@ -1,10 +1,23 @@
 use svg::node::element::Group;
 use svg::Node;
+use crate::colors::contrast_ratio;
 use crate::components::legend::LegendEntry;
+use crate::ChartError;
+use crate::views::view_class;
+
+/// The height of a single row of legend entries.
+const LEGEND_ROW_HEIGHT: usize = 20;
+/// The color of the background the legend is drawn on.
+const LEGEND_BACKGROUND_COLOR: &str = "#ffffff";
+/// The color of the border drawn around swatches that would blend into the background.
+const SWATCH_BORDER_COLOR: &str = "#bbbbbb";
+/// The contrast ratio with the background below which a swatch gets a border.
+const MIN_SWATCH_CONTRAST_RATIO: f32 = 1.5;
 
 pub(crate) struct Legend {
     width: usize,
     entries: Vec<LegendEntry>,
+    class: Option<String>,
 }
 
 impl Legend {
@@ -13,33 +26,114 @@ impl Legend {
         Self {
             entries,
             width,
+            class: None,
         }
     }
 
-    pub fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new().set("class", "g-legend");
+    /// Set a class to add to the group of the legend, e.g. to style it with an external stylesheet.
+    pub fn set_class(mut self, class: Option<String>) -> Self {
+        self.class = class;
+        self
+    }
+
+    /// Set whether swatches with a color close to the background should get a thin border.
+    pub fn set_swatch_borders(mut self, swatch_borders: bool) -> Self {
+        for entry in self.entries.iter_mut() {
+            let low_contrast = contrast_ratio(entry.get_color(), LEGEND_BACKGROUND_COLOR)
+                .is_some_and(|ratio| ratio < MIN_SWATCH_CONTRAST_RATIO);
+
+            if swatch_borders && low_contrast {
+                entry.set_swatch_border(Some(SWATCH_BORDER_COLOR.to_owned()));
+            } else {
+                entry.set_swatch_border(None);
+            }
+        }
+        self
+    }
+
+    /// Return the height of the legend, based on the number of rows its entries wrap into.
+    pub fn height(&self) -> usize {
+        match self.entry_positions().last() {
+            None => 0,
+            Some((_, row)) => (row + 1) * LEGEND_ROW_HEIGHT,
+        }
+    }
+
+    /// Compute the horizontal offset and the row of each legend entry.
+    fn entry_positions(&self) -> Vec<(usize, usize)> {
         let max_entry_length = match self.entries.iter().map(|entry| entry.get_width()).max() {
-            None => return Ok(group),
+            None => return Vec::new(),
             Some(len) => len,
         };
         let gap_between_legend_entries = 10;
-        let legend_row_height = 20;
         let mut current_row_offset = 0;
         let mut acc_row_width = 0;
+        let mut positions = Vec::new();
 
-        for entry in self.entries.iter() {
+        for _ in self.entries.iter() {
             if acc_row_width + max_entry_length > self.width && acc_row_width > 0 {
                 acc_row_width = 0;
                 current_row_offset += 1;
             }
 
-            let mut entry_group = entry.to_svg()?;
-            entry_group.assign("transform", format!("translate({},{})", acc_row_width, current_row_offset * legend_row_height));
-            group.append(entry_group);
+            positions.push((acc_row_width, current_row_offset));
 
             acc_row_width += max_entry_length + gap_between_legend_entries;
         }
 
+        positions
+    }
+
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new().set("class", view_class("g-legend", &self.class));
+
+        for (entry, (x, row)) in self.entries.iter().zip(self.entry_positions()) {
+            let mut entry_group = entry.to_svg()?;
+            entry_group.assign("transform", format!("translate({},{})", x, row * LEGEND_ROW_HEIGHT));
+            group.append(entry_group);
+        }
+
         Ok(group)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::legend::LegendMarkerType;
+
+    fn square_entry(color: &str, label: &str) -> LegendEntry {
+        LegendEntry::new(LegendMarkerType::Square, color.to_owned(), "none".to_owned(), label.to_owned())
+    }
+
+    #[test]
+    fn pale_swatches_get_a_border() {
+        let entries = vec![square_entry("#fafafa", "pale"), square_entry("#1f77b4", "dark")];
+        let svg = Legend::new(entries, 300).set_swatch_borders(true).to_svg().unwrap().to_string();
+        let swatches: Vec<&str> = svg.lines().filter(|line| line.contains("<rect")).collect();
+
+        assert_eq!(swatches.len(), 2);
+        assert!(swatches[0].contains(&format!("stroke=\"{}\"", SWATCH_BORDER_COLOR)));
+        assert!(swatches[1].contains("stroke=\"none\""));
+    }
+
+    #[test]
+    fn height_grows_with_wrapped_rows() {
+        let entries = (0..3).map(|i| square_entry("#1f77b4", &format!("series {}", i))).collect();
+        let legend = Legend::new(entries, 1000);
+        assert_eq!(legend.height(), LEGEND_ROW_HEIGHT);
+
+        let entries = (0..3).map(|i| square_entry("#1f77b4", &format!("series {}", i))).collect();
+        let legend = Legend::new(entries, 50);
+        assert_eq!(legend.height(), 3 * LEGEND_ROW_HEIGHT);
+        assert_eq!(Legend::new(Vec::new(), 50).height(), 0);
+    }
+
+    #[test]
+    fn swatch_borders_disabled_by_default() {
+        let entries = vec![square_entry("#fafafa", "pale")];
+        let svg = Legend::new(entries, 300).to_svg().unwrap().to_string();
+
+        assert!(!svg.contains(SWATCH_BORDER_COLOR));
+    }
+}
@@ -27,26 +27,43 @@ mod chart;
 mod axis;
 mod colors;
 mod components;
+mod error;
 mod legend;
 mod scales;
+mod stats;
 mod views;
 
 pub use crate::axis::{Axis, AxisPosition};
 pub use crate::chart::Chart;
-pub use crate::colors::Color;
-pub use crate::components::bar::BarLabelPosition;
-pub use crate::components::categorised_bars::CategorisedValues;
-pub use crate::components::line::LineSeries;
+pub use crate::colors::{interpolate_color, Color, DivergingColorScale};
+pub use crate::components::area::Curve;
+pub use crate::components::bar::{BarLabelPosition, BarLayout};
+pub use crate::components::legend::LegendValueMode;
+pub use crate::components::categorised_bars::{BarGroup, BarLabel, BarPosition, CategorisedValues, SeriesSet, ToF64};
+pub use crate::components::{AverageCharWidthMeasurer, TextMeasurer};
+pub use crate::error::ChartError;
+pub use crate::components::line::{LineInterpolation, LineSeries};
 pub use crate::components::scatter::{MarkerType, PointLabelPosition};
+pub use crate::components::stack::StackOffset;
 pub use crate::scales::band::ScaleBand;
+pub use crate::scales::{ContinuousScale, InvertibleScale, IterableScale, LinearScaleIter, RoundingMode, ScaleIterator, StepValue};
 pub use crate::scales::linear::ScaleLinear;
-pub use crate::scales::Scale;
+pub use crate::scales::log::LogScale;
+pub use crate::scales::pow::PowScale;
+#[cfg(feature = "chrono")]
+pub use crate::scales::time::TimeScale;
+pub use crate::scales::{infer_scale_type, Scale, ScaleType};
+pub use crate::stats::{linear_regression, quantile, LinearFit};
+pub use crate::views::annotation::AnnotationView;
 pub use crate::views::area::AreaSeriesView;
+pub use crate::views::box_plot::BoxPlotView;
 pub use crate::views::datum::{BarDatum, PointDatum};
+pub use crate::views::difference_bar::DifferenceBarView;
 pub use crate::views::horizontal_bar::HorizontalBarView;
 pub use crate::views::line::LineSeriesView;
 pub use crate::views::scatter::ScatterView;
 pub use crate::views::vertical_bar::VerticalBarView;
+pub use crate::views::View;
 
 #[cfg(test)]
 mod tests {
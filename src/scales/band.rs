@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
-use crate::scales::{Scale, ScaleType};
+use crate::scales::{Scale, ScaleType, TickFormatter};
 
 /// The scale to represent categorical data.
 #[derive(Debug)]
@@ -29,6 +29,12 @@ pub struct ScaleBand {
     r0: f32,
     /// The end value of the range.
     r1: f32,
+    /// Whether the first domain entry is mapped to the end of the range instead of the start.
+    reversed: bool,
+    /// The positions of domain entries that are placed explicitly instead of evenly spaced.
+    explicit_positions: HashMap<String, f32>,
+    /// The closure that formats the ticks into labels, if any.
+    tick_formatter: Option<TickFormatter<String>>,
 }
 
 impl ScaleBand {
@@ -46,9 +52,21 @@ impl ScaleBand {
             align: 0.5,
             r0: 0f32,
             r1: 0f32,
+            reversed: false,
+            explicit_positions: HashMap::new(),
+            tick_formatter: None,
         }
     }
 
+    /// Set whether the order of the domain entries should be reversed along the range.
+    /// This is useful for e.g. horizontal bar charts, where the first category should
+    /// be displayed at the top of the chart.
+    pub fn set_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self.rescale();
+        self
+    }
+
     /// Set the inner padding ratio.
     pub fn set_inner_padding(mut self, padding: f32) -> Self {
         self.padding_inner = padding;
@@ -63,7 +81,8 @@ impl ScaleBand {
         self
     }
 
-    /// Set the domain limits for the scale band.
+    /// Set the domain limits for the scale band. Any explicit positions are cleared,
+    /// since they might not list every entry of the new domain.
     pub fn set_domain(mut self, range: Vec<String>) -> Self {
         // Deduplicate the domain range and keep order of entries.
         let mut unique = Vec::new();
@@ -78,15 +97,36 @@ impl ScaleBand {
         }
 
         self.domain = unique;
+        self.explicit_positions.clear();
         self.rescale();
         self
     }
 
+    /// Set explicit positions in the range for the domain entries, e.g. to place the events
+    /// of a timeline at irregular intervals. Every entry of the domain has to be listed, so
+    /// the domain should be set first; setting it afterwards clears the explicit positions.
+    pub fn set_explicit_positions(mut self, positions: Vec<(String, f32)>) -> Result<Self, String> {
+        let positions = positions.into_iter().collect::<HashMap<String, f32>>();
+        if let Some(missing) = self.domain.iter().find(|entry| !positions.contains_key(*entry)) {
+            return Err(format!("No explicit position was given for the domain entry \"{}\".", missing));
+        }
+
+        self.explicit_positions = positions;
+        self.rescale();
+        Ok(self)
+    }
+
     /// Get the domain limits of the scale.
     pub fn domain(&self) -> &Vec<String> {
         &self.domain
     }
 
+    /// Set the closure that formats the ticks into their labels, instead of their `Display` representation.
+    pub fn set_tick_formatter(mut self, formatter: impl Fn(&String) -> String + 'static) -> Self {
+        self.tick_formatter = Some(TickFormatter::new(formatter));
+        self
+    }
+
     /// Set the range limits for the scale band.
     pub fn set_range(mut self, range: Vec<isize>) -> Self {
         self.range = range;
@@ -103,12 +143,14 @@ impl ScaleBand {
         let n = self.domain.len();
         let r0 = self.range[0];
         let r1 = self.range[1];
-        let reverse = r1 < r0;
         let mut start = r0 as f32;
         let mut stop = r1 as f32;
 
-        if reverse {
+        // A range given in reversed order is stored in ascending order and remembered
+        // as a reversed scale, so that subsequent rescales keep the reversed order.
+        if r1 < r0 {
             self.range = vec![r1, r0];
+            self.reversed = true;
             start = r1 as f32;
             stop = r0 as f32;
         }
@@ -134,7 +176,7 @@ impl ScaleBand {
             self.offsets.push(start + self.step * i as f32);
         }
 
-        if reverse {
+        if self.reversed {
             self.offsets.reverse();
         }
 
@@ -150,6 +192,12 @@ impl ScaleBand {
         // Re-assign domains with any duplicates removed.
         self.domain.clear();
         self.domain = processed_domains;
+
+        for (domain, position) in self.explicit_positions.iter() {
+            if let Some(offset_index) = self.index.get(domain) {
+                self.offsets[*offset_index] = *position;
+            }
+        }
     }
 }
 
@@ -171,16 +219,123 @@ impl Scale<String> for ScaleBand {
 
     /// Get the start range value.
     fn range_start(&self) -> f32 {
-        self.range[0] as f32
+        if self.reversed {
+            self.range[1] as f32
+        } else {
+            self.range[0] as f32
+        }
     }
 
     /// Get the end range value.
     fn range_end(&self) -> f32 {
-        self.range[1] as f32
+        if self.reversed {
+            self.range[0] as f32
+        } else {
+            self.range[1] as f32
+        }
     }
 
     /// Get the list of ticks that represent the scale on a chart axis.
     fn get_ticks(&self) -> Vec<String> {
         self.domain.clone()
     }
-}
\ No newline at end of file
+
+    /// Get the label of the given tick.
+    fn format_tick(&self, value: &String) -> String {
+        TickFormatter::format(&self.tick_formatter, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labeled_ticks_are_centered_in_bands() {
+        let scale = ScaleBand::new()
+            .set_domain(vec![String::from("A"), String::from("B")])
+            .set_inner_padding(0_f32)
+            .set_outer_padding(0_f32)
+            .set_range(vec![0, 200]);
+
+        assert_eq!(
+            scale.labeled_ticks(),
+            vec![(50_f32, String::from("A")), (150_f32, String::from("B"))]
+        );
+    }
+
+    #[test]
+    fn reversed_band_order() {
+        let domain = vec![String::from("A"), String::from("B"), String::from("C")];
+        let scale = ScaleBand::new()
+            .set_domain(domain.clone())
+            .set_range(vec![0, 300]);
+        let reversed = ScaleBand::new()
+            .set_domain(domain.clone())
+            .set_range(vec![0, 300])
+            .set_reversed(true);
+
+        assert!(!scale.is_range_reversed());
+        assert!(reversed.is_range_reversed());
+        assert!(scale.scale(&domain[0]) < scale.scale(&domain[2]));
+        assert!(reversed.scale(&domain[0]) > reversed.scale(&domain[1]));
+        assert!(reversed.scale(&domain[1]) > reversed.scale(&domain[2]));
+        assert_eq!(reversed.scale(&domain[0]), scale.scale(&domain[2]));
+        assert_eq!(reversed.scale(&domain[2]), scale.scale(&domain[0]));
+    }
+
+    #[test]
+    fn reversed_range_survives_rescale() {
+        let domain = vec![String::from("A"), String::from("B"), String::from("C")];
+        let scale = ScaleBand::new()
+            .set_range(vec![300, 0])
+            .set_domain(domain.clone());
+
+        assert!(scale.is_range_reversed());
+        assert!(scale.scale(&domain[0]) > scale.scale(&domain[2]));
+    }
+
+    #[test]
+    fn explicit_positions_of_irregular_events() {
+        let domain = vec![String::from("launch"), String::from("review"), String::from("release")];
+        let scale = ScaleBand::new()
+            .set_domain(domain.clone())
+            .set_range(vec![0, 300])
+            .set_explicit_positions(vec![
+                (String::from("launch"), 10_f32),
+                (String::from("review"), 200_f32),
+                (String::from("release"), 210_f32),
+            ])
+            .unwrap();
+
+        assert_eq!(scale.scale(&domain[0]), 10_f32);
+        assert_eq!(scale.scale(&domain[1]), 200_f32);
+        assert_eq!(scale.scale(&domain[2]), 210_f32);
+        assert_eq!(scale.set_range(vec![0, 600]).scale(&domain[1]), 200_f32);
+
+        let missing = ScaleBand::new()
+            .set_domain(domain)
+            .set_explicit_positions(vec![(String::from("launch"), 10_f32)]);
+        assert_eq!(missing.unwrap_err(), "No explicit position was given for the domain entry \"review\".");
+    }
+
+    #[test]
+    fn new_domain_clears_explicit_positions() {
+        let domain = vec![String::from("launch"), String::from("review")];
+        let extended = vec![String::from("launch"), String::from("review"), String::from("release")];
+        let evenly_spaced = ScaleBand::new()
+            .set_domain(extended.clone())
+            .set_range(vec![0, 300]);
+
+        let scale = ScaleBand::new()
+            .set_domain(domain)
+            .set_range(vec![0, 300])
+            .set_explicit_positions(vec![(String::from("launch"), 10_f32), (String::from("review"), 200_f32)])
+            .unwrap()
+            .set_domain(extended.clone());
+
+        for entry in extended.iter() {
+            assert_eq!(scale.scale(entry), evenly_spaced.scale(entry));
+        }
+    }
+}
@@ -1,14 +1,63 @@
-use super::{Continuous, Dimension, IterableScale, LinearScaleIter};
+use super::{Continuous, Dimension, InvertibleScale, IterableScale, LinearScaleIter, ScaleIterator, ScaleType};
 
+/// Define how a scaled value is rounded to a whole dimension.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RoundingMode {
+    /// Round half-way cases away from zero.
+    Round,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round towards zero.
+    Truncate,
+}
+
+impl RoundingMode {
+    fn apply(&self, value: Continuous) -> Continuous {
+        match self {
+            RoundingMode::Round => value.round(),
+            RoundingMode::Floor => value.floor(),
+            RoundingMode::Ceil => value.ceil(),
+            RoundingMode::Truncate => value.trunc(),
+        }
+    }
+}
+
+/// The step between approximately `count` ticks from `min` to `max`, rounded to
+/// 1, 2 or 5 times a power of ten.
+pub(crate) fn nice_step(min: Continuous, max: Continuous, count: usize) -> Continuous {
+    let rough_step = (max - min) / count as Continuous;
+    let power = rough_step.log10().floor();
+    let error = rough_step / (10.0 as Continuous).powf(power);
+    let factor = if error >= (50.0 as Continuous).sqrt() {
+        10.0
+    } else if error >= (10.0 as Continuous).sqrt() {
+        5.0
+    } else if error >= (2.0 as Continuous).sqrt() {
+        2.0
+    } else {
+        1.0
+    };
+
+    factor * (10.0 as Continuous).powf(power)
+}
+
+/// The scale to represent numeric data on a range of whole dimensions, from the offset up to
+/// the offset plus the dimension. A domain whose start is greater than its end is mirrored.
 #[derive(Debug)]
-struct ContinuousScale {
+pub struct ContinuousScale {
     start: Continuous,
     end: Continuous,
     min: Continuous,
     max: Continuous,
     size_dimension_ratio: Continuous,
     dimension_size_ratio: Continuous,
+    rounding: RoundingMode,
+    clamped: bool,
+    step: Option<Continuous>,
 
+    dimension: Dimension,
     offset: Dimension,
 }
 
@@ -20,13 +69,16 @@ impl ContinuousScale {
             (end, start)
         };
 
-        let size_float: Continuous = end - start;
+        // Like the discrete scale, a zero-length domain is treated as a domain of one unit
+        // to avoid dividing by zero, so that its start is scaled to the offset.
+        let size_float: Continuous = if start == end { 1.0 } else { end - start };
         let dimension_float: Continuous = dimension.into();
 
         let size_dimension_ratio = size_float / dimension_float;
         let dimension_size_ratio = dimension_float / size_float;
 
         Self {
+            dimension,
             offset: 0,
             start,
             end,
@@ -34,6 +86,9 @@ impl ContinuousScale {
             max,
             size_dimension_ratio,
             dimension_size_ratio,
+            rounding: RoundingMode::Round,
+            clamped: false,
+            step: None,
         }
     }
 
@@ -42,28 +97,194 @@ impl ContinuousScale {
 
         self
     }
+
+    /// Set the `RoundingMode` that `scale()` applies to the fractional pixel of a value.
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+
+        self
+    }
+
+    /// Iterate over the domain in steps of the given size instead of a step per dimension.
+    /// The step follows the direction of the domain, a step that is not positive is ignored.
+    pub fn with_step(mut self, step: Continuous) -> Self {
+        self.step = if step > 0.0 { Some(step) } else { None };
+
+        self
+    }
+
+    /// The signed distance between the values yielded by `iter()`.
+    fn iter_step(&self) -> Continuous {
+        match self.step {
+            Some(step) if self.start > self.end => -step,
+            Some(step) => step,
+            None => self.size_dimension_ratio,
+        }
+    }
+
+    /// The ticks that divide each step of `iter()` into `per_major` equal parts,
+    /// without the values of the steps themselves.
+    pub fn minor_ticks(&self, per_major: usize) -> Vec<Continuous> {
+        let majors = self.iter().collect::<Vec<Continuous>>();
+        let minor_step = self.iter_step() / per_major.max(1) as Continuous;
+
+        majors
+            .windows(2)
+            .flat_map(|pair| (1..per_major).map(move |index| pair[0] + index as Continuous * minor_step))
+            .collect()
+    }
+
+    /// Clamp values outside of the domain, so that they are scaled to the range extremes.
+    pub fn clamp(mut self, enabled: bool) -> Self {
+        self.clamped = enabled;
+
+        self
+    }
+
+    /// Approximately `count` evenly spaced ticks within the domain, at a step of 1, 2 or 5 times
+    /// a power of ten. The ticks are ordered from the start to the end of the domain.
+    pub fn ticks(&self, count: usize) -> Vec<Continuous> {
+        if count == 0 {
+            return Vec::new();
+        }
+        if self.min == self.max {
+            return vec![self.start];
+        }
+
+        let step = nice_step(self.min, self.max, count);
+
+        // Compute each tick from its index to avoid accumulating rounding errors.
+        let first = (self.min / step).ceil() as i64;
+        let last = (self.max / step).floor() as i64;
+        let mut ticks: Vec<Continuous> = (first..=last).map(|index| index as Continuous * step).collect();
+
+        if self.start > self.end {
+            ticks.reverse();
+        }
+
+        ticks
+    }
+
+    /// Expand the domain outward to multiples of the step between about ten ticks, e.g. from
+    /// 0.37..9.84 to 0..10. Bounds that are already a multiple of the step are kept.
+    pub fn nice(mut self) -> Self {
+        if self.min == self.max {
+            return self;
+        }
+
+        let step = nice_step(self.min, self.max, 10);
+        let dimension: Continuous = self.dimension.into();
+        let min = Self::snap_to_step(self.min, step, Continuous::floor);
+        let max = Self::snap_to_step(self.max, step, Continuous::ceil);
+        let (start, end) = if self.start < self.end { (min, max) } else { (max, min) };
+
+        self.start = start;
+        self.end = end;
+        self.min = min;
+        self.max = max;
+        self.size_dimension_ratio = (end - start) / dimension;
+        self.dimension_size_ratio = dimension / (end - start);
+
+        self
+    }
+
+    /// Round the value to a multiple of the step, unless it is one within the precision of a float.
+    fn snap_to_step(value: Continuous, step: Continuous, round: fn(Continuous) -> Continuous) -> Continuous {
+        let steps = value / step;
+        if (steps - steps.round()).abs() < 1e-4 {
+            value
+        } else {
+            round(steps) * step
+        }
+    }
+
+    /// The signed distance from the start to the end of the domain.
+    pub fn span(&self) -> Continuous {
+        self.end - self.start
+    }
+
+    /// Every whole dimension of the range, from the offset up to and including its end,
+    /// paired with the domain value that it inverts to.
+    pub fn pixel_domain_pairs(&self) -> impl Iterator<Item = (Dimension, Continuous)> + '_ {
+        (self.offset..=self.offset.saturating_add(self.dimension)).map(move |pixel| (pixel, self.invert(pixel)))
+    }
+
+    /// The position of zero, e.g. to draw a baseline, or `None` when zero is outside of the domain.
+    pub fn zero_position(&self) -> Option<Dimension> {
+        if self.min <= 0.0 && 0.0 <= self.max {
+            Some(self.scale(0.0))
+        } else {
+            None
+        }
+    }
 }
 
 impl IterableScale<Continuous> for ContinuousScale {
     fn contains(&self, value: Continuous) -> bool {
-        self.min < value && value < self.max
+        self.min <= value && value <= self.max
     }
 
     fn scale(&self, value: Continuous) -> Dimension {
+        let value = if self.clamped {
+            value.clamp(self.min, self.max)
+        } else {
+            value
+        };
         let distance = value - self.start;
         let distance_float: Continuous = distance;
         let scaled: Dimension =
-            Continuous::round(distance_float * self.dimension_size_ratio) as Dimension;
+            self.rounding.apply(distance_float * self.dimension_size_ratio) as Dimension;
 
         self.offset + scaled
     }
 
-    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = Continuous> + 'i> {
-        Box::new(LinearScaleIter::new(
-            self.start,
-            self.end,
-            self.size_dimension_ratio,
-        ))
+    fn iter<'i>(&'i self) -> Box<dyn ScaleIterator<Continuous> + 'i> {
+        Box::new(LinearScaleIter::new(self.start, self.end, self.iter_step()))
+    }
+}
+
+/// The views and axes of a chart see the domain from its minimum to its maximum, so that a
+/// mirrored domain shows as a reversed range, like that of a `ScaleLinear` on a Y axis.
+impl super::Scale<Continuous> for ContinuousScale {
+    fn get_type(&self) -> ScaleType {
+        ScaleType::Linear
+    }
+
+    fn scale(&self, domain: &Continuous) -> f32 {
+        IterableScale::scale(self, *domain) as f32
+    }
+
+    fn bandwidth(&self) -> Option<f32> {
+        Some(0_f32)
+    }
+
+    fn range_start(&self) -> f32 {
+        IterableScale::scale(self, self.min) as f32
+    }
+
+    fn range_end(&self) -> f32 {
+        IterableScale::scale(self, self.max) as f32
+    }
+
+    fn domain_limits(&self) -> Option<(f32, f32)> {
+        Some((self.min, self.max))
+    }
+
+    fn as_numeric(&self) -> Option<&dyn super::Scale<Continuous>> {
+        Some(self)
+    }
+
+    /// About ten ticks, at the same step as the bounds of `nice()`.
+    fn get_ticks(&self) -> Vec<Continuous> {
+        self.ticks(10)
+    }
+}
+
+impl InvertibleScale<Continuous> for ContinuousScale {
+    fn invert(&self, dimension: Dimension) -> Continuous {
+        let distance = dimension as Continuous - self.offset as Continuous;
+
+        self.start + distance * self.size_dimension_ratio
     }
 }
 
@@ -88,14 +309,15 @@ where
 #[test]
 fn iterate_over_continuous_scale() {
     let continuous = ContinuousScale::new(800, 0.0, 360.0).offset(400);
-    assert_eq!(continuous.iter().count(), 800);
+    assert_eq!(continuous.iter().count(), 801);
     let expected = vec![
         (0.0, 400),
         (0.45, 401),
         (0.9, 402),
-        (358.65158, 1197),
-        (359.1016, 1198),
-        (359.5516, 1199),
+        (358.65, 1197),
+        (359.1, 1198),
+        (359.55, 1199),
+        (360.0, 1200),
     ];
     assert_eq!(sample(&continuous, 796), expected);
 }
@@ -139,16 +361,277 @@ fn iterate_over_continuous_mirrored_plus_minus_scale() {
 #[test]
 fn iterate_over_continuous_plus_minus_scale() {
     let continuous = ContinuousScale::new(300, -500.0, 500.0);
-    assert_eq!(continuous.iter().count(), 300);
+    assert_eq!(continuous.iter().count(), 301);
 
     let expected = vec![
         (-500.0, 0),
         (-496.66666, 1),
-        (-493.3333, 2),
-        (490.00116, 297),
-        (493.3345, 298),
-        (496.66785, 299),
+        (-493.33334, 2),
+        (490.0, 297),
+        (493.3333, 298),
+        (496.6667, 299),
+        (500.0, 300),
     ];
 
     assert_eq!(sample(&continuous, 296), expected);
 }
+
+#[test]
+fn iterate_backwards_over_continuous_scales() {
+    let scales = vec![
+        ContinuousScale::new(800, 0.0, 360.0).offset(400),
+        ContinuousScale::new(720, 360.0, 0.0),
+        ContinuousScale::new(720, 360.0, -360.0),
+        ContinuousScale::new(300, -500.0, 500.0),
+    ];
+
+    for continuous in scales.iter() {
+        let forward = continuous.iter().collect::<Vec<Continuous>>();
+        let mut backward = continuous.iter().rev().collect::<Vec<Continuous>>();
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+}
+
+#[test]
+fn iterate_from_both_ends_of_continuous_scale() {
+    let continuous = ContinuousScale::new(4, 0.0, 4.0);
+    let mut iter = continuous.iter();
+
+    assert_eq!(iter.next(), Some(0.0));
+    assert_eq!(iter.next_back(), Some(4.0));
+    assert_eq!(iter.next(), Some(1.0));
+    assert_eq!(iter.next_back(), Some(3.0));
+    assert_eq!(iter.next(), Some(2.0));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn exact_size_of_continuous_scale_iteration() {
+    let continuous = ContinuousScale::new(800, 0.0, 360.0).offset(400);
+    assert_eq!(continuous.iter().len(), 801);
+
+    let mirrored = ContinuousScale::new(720, 360.0, 0.0);
+    let mut iter = mirrored.iter();
+    assert_eq!(iter.size_hint(), (721, Some(721)));
+    iter.next();
+    iter.next();
+    assert_eq!(iter.len(), 719);
+    iter.next_back();
+    assert_eq!(iter.len(), 718);
+    assert_eq!(iter.count(), 718);
+
+    let plus_minus = ContinuousScale::new(300, -500.0, 500.0);
+    assert_eq!(plus_minus.iter().len(), plus_minus.iter().count());
+}
+
+#[test]
+fn domain_bounds_are_contained() {
+    let continuous = ContinuousScale::new(800, 0.0, 360.0);
+    assert!(continuous.contains(0.0));
+    assert!(continuous.contains(360.0));
+    assert!(!continuous.contains(-0.5));
+    assert!(!continuous.contains(360.5));
+
+    let mirrored = ContinuousScale::new(720, 360.0, -360.0);
+    assert!(mirrored.contains(360.0));
+    assert!(mirrored.contains(-360.0));
+}
+
+#[test]
+fn zero_length_domain() {
+    let continuous = ContinuousScale::new(800, 5.0, 5.0).offset(100);
+    assert_eq!(continuous.scale(5.0), 100);
+    assert_eq!(continuous.scale(6.0), 900);
+    assert_eq!(continuous.invert(100), 5.0);
+    assert_eq!(continuous.span(), 0.0);
+    assert_eq!(continuous.iter().collect::<Vec<Continuous>>(), vec![5.0]);
+}
+
+#[test]
+fn iterate_with_explicit_step() {
+    let continuous = ContinuousScale::new(400, 0.0, 1.0).with_step(0.25);
+    assert_eq!(continuous.iter().collect::<Vec<Continuous>>(), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(continuous.minor_ticks(2), vec![0.125, 0.375, 0.625, 0.875]);
+    assert_eq!(continuous.minor_ticks(1), Vec::<Continuous>::new());
+
+    let mirrored = ContinuousScale::new(400, 1.0, 0.0).with_step(0.5);
+    assert_eq!(mirrored.iter().collect::<Vec<Continuous>>(), vec![1.0, 0.5, 0.0]);
+
+    assert_eq!(ContinuousScale::new(100, 0.0, 1.0).with_step(0.0).iter().len(), 101);
+    assert_eq!(ContinuousScale::new(100, 0.0, 1.0).with_step(-0.25).iter().len(), 101);
+}
+
+#[test]
+fn span_of_domain() {
+    assert_eq!(ContinuousScale::new(800, 0.0, 360.0).span(), 360.0);
+    assert_eq!(ContinuousScale::new(720, 360.0, 0.0).span(), -360.0);
+    assert_eq!(ContinuousScale::new(720, 360.0, -360.0).span(), -720.0);
+}
+
+#[test]
+fn zero_position_within_domain() {
+    assert_eq!(ContinuousScale::new(800, -50.0, 50.0).zero_position(), Some(400));
+    assert_eq!(ContinuousScale::new(800, -50.0, 50.0).offset(100).zero_position(), Some(500));
+    assert_eq!(ContinuousScale::new(720, 360.0, 0.0).zero_position(), Some(720));
+    assert_eq!(ContinuousScale::new(800, 20.0, 100.0).zero_position(), None);
+}
+
+#[test]
+fn rounding_modes_at_half_pixel() {
+    let continuous = ContinuousScale::new(100, 0.0, 100.0);
+    assert_eq!(continuous.scale(10.5), 11);
+
+    let continuous = ContinuousScale::new(100, 0.0, 100.0).with_rounding(RoundingMode::Round);
+    assert_eq!(continuous.scale(10.5), 11);
+
+    let continuous = ContinuousScale::new(100, 0.0, 100.0).with_rounding(RoundingMode::Floor);
+    assert_eq!(continuous.scale(10.5), 10);
+
+    let continuous = ContinuousScale::new(100, 0.0, 100.0).with_rounding(RoundingMode::Ceil);
+    assert_eq!(continuous.scale(10.5), 11);
+
+    let continuous = ContinuousScale::new(100, 0.0, 100.0).with_rounding(RoundingMode::Truncate);
+    assert_eq!(continuous.scale(10.5), 10);
+}
+
+#[test]
+fn rounding_modes_on_mirrored_scale() {
+    let continuous = ContinuousScale::new(100, 100.0, 0.0).with_rounding(RoundingMode::Floor);
+    assert_eq!(continuous.scale(89.5), 10);
+
+    let continuous = ContinuousScale::new(100, 100.0, 0.0).with_rounding(RoundingMode::Ceil);
+    assert_eq!(continuous.scale(89.5), 11);
+}
+
+#[cfg(test)]
+fn assert_round_trip(continuous: &ContinuousScale) {
+    // Scaling rounds to whole dimensions, so inverting recovers the value within half a dimension.
+    let tolerance = continuous.size_dimension_ratio.abs() / 2.0 + 1e-3;
+    for value in continuous.iter() {
+        let recovered = continuous.invert(continuous.scale(value));
+        assert!(
+            (recovered - value).abs() <= tolerance,
+            "{} was recovered as {}",
+            value,
+            recovered
+        );
+    }
+}
+
+#[test]
+fn invert_recovers_scaled_values() {
+    assert_round_trip(&ContinuousScale::new(800, 0.0, 360.0).offset(400));
+    assert_round_trip(&ContinuousScale::new(720, 360.0, 0.0));
+    assert_round_trip(&ContinuousScale::new(720, 360.0, -360.0));
+    assert_round_trip(&ContinuousScale::new(300, -500.0, 500.0));
+}
+
+#[test]
+fn invert_mirrored_scale() {
+    let continuous = ContinuousScale::new(720, 360.0, 0.0).offset(10);
+    assert_eq!(continuous.invert(10), 360.0);
+    assert_eq!(continuous.invert(370), 180.0);
+    assert_eq!(continuous.invert(730), 0.0);
+}
+
+#[test]
+fn pairs_of_pixels_and_domain_values() {
+    let continuous = ContinuousScale::new(40, 0.0, 10.0).offset(20);
+    let pairs = continuous.pixel_domain_pairs().collect::<Vec<(Dimension, Continuous)>>();
+
+    assert_eq!(pairs.len(), 41);
+    assert_eq!(pairs.first(), Some(&(20, 0.0)));
+    assert_eq!(pairs[2], (22, 0.5));
+    assert_eq!(pairs.last(), Some(&(60, 10.0)));
+
+    let mirrored = ContinuousScale::new(4, 8.0, 0.0);
+    assert_eq!(
+        mirrored.pixel_domain_pairs().collect::<Vec<(Dimension, Continuous)>>(),
+        vec![(0, 8.0), (1, 6.0), (2, 4.0), (3, 2.0), (4, 0.0)]
+    );
+}
+
+#[test]
+fn nice_domain_bounds() {
+    let continuous = ContinuousScale::new(800, 0.37, 9.84).offset(100).nice();
+    assert_eq!((continuous.start, continuous.end), (0.0, 10.0));
+    assert_eq!(continuous.scale(5.0), 500);
+
+    let continuous = ContinuousScale::new(800, 0.0, 9.84).nice();
+    assert_eq!((continuous.start, continuous.end), (0.0, 10.0));
+
+    let continuous = ContinuousScale::new(900, 103.0, 998.0).nice();
+    assert_eq!((continuous.start, continuous.end), (100.0, 1000.0));
+    assert_eq!(continuous.scale(550.0), 450);
+
+    let continuous = ContinuousScale::new(600, -0.3, 0.3).nice();
+    assert_eq!((continuous.start, continuous.end), (-0.3, 0.3));
+
+    let continuous = ContinuousScale::new(800, 0.0, 100.0).nice();
+    assert_eq!((continuous.start, continuous.end), (0.0, 100.0));
+}
+
+#[test]
+fn nice_domain_bounds_of_mirrored_scale() {
+    let continuous = ContinuousScale::new(400, 998.0, 103.0).nice();
+    assert_eq!((continuous.start, continuous.end), (1000.0, 100.0));
+    assert_eq!(continuous.scale(1000.0), 0);
+    assert_eq!(continuous.scale(100.0), 400);
+
+    let continuous = ContinuousScale::new(400, 3.0, 3.0).nice();
+    assert_eq!((continuous.start, continuous.end), (3.0, 3.0));
+}
+
+#[test]
+fn clamp_to_range_extremes() {
+    let continuous = ContinuousScale::new(800, 0.0, 360.0).offset(400).clamp(true);
+    assert_eq!(continuous.scale(-10.0), 400);
+    assert_eq!(continuous.scale(400.0), 1200);
+    assert_eq!(continuous.scale(180.0), 800);
+    assert!(!continuous.contains(400.0));
+
+    let mirrored = ContinuousScale::new(720, 360.0, 0.0).clamp(true);
+    assert_eq!(mirrored.scale(400.0), 0);
+    assert_eq!(mirrored.scale(-5.0), 720);
+
+    let unclamped = ContinuousScale::new(800, 0.0, 360.0).offset(400);
+    assert_eq!(unclamped.scale(400.0), 1289);
+}
+
+#[test]
+fn nice_ticks() {
+    let continuous = ContinuousScale::new(800, 0.0, 100.0);
+    assert_eq!(continuous.ticks(5), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+    assert_eq!(continuous.ticks(10), vec![0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0]);
+    assert_eq!(continuous.ticks(2), vec![0.0, 50.0, 100.0]);
+    assert_eq!(continuous.ticks(0), Vec::<Continuous>::new());
+
+    let continuous = ContinuousScale::new(300, -0.35, 0.35);
+    assert_eq!(continuous.ticks(4), vec![-0.2, 0.0, 0.2]);
+}
+
+#[test]
+fn nice_ticks_of_mirrored_scale() {
+    let continuous = ContinuousScale::new(720, 360.0, -360.0);
+    assert_eq!(continuous.ticks(4), vec![200.0, 0.0, -200.0]);
+
+    let continuous = ContinuousScale::new(720, 360.0, 360.0);
+    assert_eq!(continuous.ticks(4), vec![360.0]);
+}
+
+#[test]
+fn continuous_scale_on_a_chart_axis() {
+    use super::Scale;
+
+    let scale: &dyn Scale<f32> = &ContinuousScale::new(400, 100.0, 0.0).offset(20);
+    assert_eq!(scale.get_type(), ScaleType::Linear);
+    assert_eq!(scale.scale(&25.0), 320.0);
+    assert_eq!(scale.domain_limits(), Some((0.0, 100.0)));
+    assert_eq!((scale.range_start(), scale.range_end()), (420.0, 20.0));
+    assert!(scale.is_range_reversed());
+    assert_eq!(scale.get_ticks().len(), 11);
+    assert_eq!(scale.labeled_ticks()[0], (20.0, String::from("100")));
+    assert_eq!(scale.labeled_ticks()[10], (420.0, String::from("0")));
+}
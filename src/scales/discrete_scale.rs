@@ -3,7 +3,7 @@ use std::{
     ops::{Add, Sub},
 };
 
-use super::{Dimension, IterableScale, LinearScaleIter};
+use super::{Dimension, IterableScale, LinearScaleIter, ScaleIterator};
 
 type Discrete = i64;
 
@@ -15,7 +15,7 @@ struct DiscreteScale {
     max: Discrete,
     step: Discrete,
 
-    units_per_step: Dimension,
+    units_per_step: i64,
     offset: Dimension,
 }
 
@@ -34,10 +34,11 @@ impl DiscreteScale {
 
         let size = end - start;
         let dim_64: i64 = dimension.into();
-        let (step, units_per_step) = if size > dim_64 {
+        // Keep the arithmetic in 64 bits, so that large dimensions cannot overflow.
+        let (step, units_per_step) = if size.abs() > dim_64 {
             (size / dim_64, 1)
         } else {
-            (1, dim_64 as u16 / size as u16)
+            (size.signum(), dim_64 / size.abs())
         };
 
         Self {
@@ -63,18 +64,34 @@ impl DiscreteScale {
 
         self
     }
+
+    /// The signed distance from the start to the end of the domain.
+    pub fn span(&self) -> Discrete {
+        self.end - self.start
+    }
+
+    /// The position of zero, e.g. to draw a baseline, or `None` when zero is outside of the domain.
+    pub fn zero_position(&self) -> Option<Dimension> {
+        if self.min <= 0 && 0 <= self.max {
+            Some(self.scale(0))
+        } else {
+            None
+        }
+    }
 }
 
 impl IterableScale<Discrete> for DiscreteScale {
     fn contains(&self, value: Discrete) -> bool {
-        self.min < value && value < self.max && (value - self.start) % self.step == 0
+        self.min <= value && value <= self.max && (value - self.start) % self.step == 0
     }
 
     fn scale(&self, value: Discrete) -> Dimension {
-        self.offset + ((((value - self.start) / self.step) as u16) * self.units_per_step)
+        let position = i64::from(self.offset) + (value - self.start) / self.step * self.units_per_step;
+
+        position.clamp(0, Dimension::MAX.into()) as Dimension
     }
 
-    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = Discrete> + 'i> {
+    fn iter<'i>(&'i self) -> Box<dyn ScaleIterator<Discrete> + 'i> {
         Box::new(LinearScaleIter::new(self.start, self.end, self.step))
     }
 }
@@ -131,3 +148,52 @@ fn discrete_2() {
         vec![(-300, 0), (-292, 1), (-284, 2), (476, 97), (484, 98), (492, 99), (500, 100)]
     );
 }
+
+#[test]
+fn exact_size_of_discrete_scale_iteration() {
+    let discrete = DiscreteScale::new(800, 0, 100).offset(400);
+    let mut iter = discrete.iter();
+    assert_eq!(iter.len(), 101);
+    iter.nth(49);
+    assert_eq!(iter.len(), 51);
+    assert_eq!(iter.last(), Some(100));
+
+    let reversed = DiscreteScale::new(100, 500, -300).with_step(-8);
+    assert_eq!(reversed.iter().len(), 101);
+    assert_eq!(reversed.iter().len(), reversed.iter().count());
+}
+
+#[test]
+fn zero_position_within_domain() {
+    assert_eq!(DiscreteScale::new(100, -50, 50).zero_position(), Some(50));
+    assert_eq!(DiscreteScale::new(100, 20, 100).zero_position(), None);
+}
+
+#[test]
+fn domain_bounds_are_contained() {
+    let discrete = DiscreteScale::new(800, 0, 100);
+    assert!(discrete.contains(0));
+    assert!(discrete.contains(100));
+    assert!(!discrete.contains(101));
+
+    let reversed = DiscreteScale::new(100, 500, -300).with_step(-8);
+    assert!(reversed.contains(500));
+    assert!(reversed.contains(-300));
+    assert!(!reversed.contains(-301));
+}
+
+#[test]
+fn large_dimension_over_small_domain() {
+    let discrete = DiscreteScale::new(4000, 0, 3);
+    assert_eq!(discrete.iter().map(|value| discrete.scale(value)).collect::<Vec<Dimension>>(), vec![0, 1333, 2666, 3999]);
+
+    let offset = DiscreteScale::new(4000, 0, 3).offset(60_000);
+    assert_eq!(offset.scale(3), 63_999);
+    assert_eq!(offset.scale(5), Dimension::MAX);
+}
+
+#[test]
+fn span_of_domain() {
+    assert_eq!(DiscreteScale::new(800, 0, 100).span(), 100);
+    assert_eq!(DiscreteScale::new(100, 500, -300).span(), -800);
+}
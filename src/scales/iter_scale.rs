@@ -1,12 +1,27 @@
 use super::Dimension;
 
+/// The iterator over the domain values of a scale, which can be walked from both ends
+/// and knows how many values remain.
+pub trait ScaleIterator<DR>: DoubleEndedIterator<Item = DR> + ExactSizeIterator {}
+
+impl<DR, I> ScaleIterator<DR> for I where I: DoubleEndedIterator<Item = DR> + ExactSizeIterator {}
+
 pub trait IterableScale<DR>
 where
     DR: Copy + Default + PartialOrd + PartialEq,
 {
+    /// Whether the value lies within the domain, including its start and end.
     fn contains(&self, value: DR) -> bool;
 
     fn scale(&self, value: DR) -> Dimension;
 
-    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = DR> + 'i>;
+    fn iter<'i>(&'i self) -> Box<dyn ScaleIterator<DR> + 'i>;
+}
+
+pub trait InvertibleScale<DR>: IterableScale<DR>
+where
+    DR: Copy + Default + PartialOrd + PartialEq,
+{
+    /// The domain value that is scaled to the given dimension.
+    fn invert(&self, dimension: Dimension) -> DR;
 }
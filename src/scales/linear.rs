@@ -1,5 +1,5 @@
 use std::cmp::{max, Ordering};
-use crate::scales::{Scale, ScaleType};
+use crate::scales::{Scale, ScaleType, TickFormatter};
 
 /// The scale to represent categorical data.
 #[derive(Debug)]
@@ -10,6 +10,8 @@ pub struct ScaleLinear {
     range: Vec<isize>,
     /// The amount of ticks to display.
     tick_count: usize,
+    /// The closure that formats the ticks into labels, if any.
+    tick_formatter: Option<TickFormatter<f32>>,
 }
 
 impl ScaleLinear {
@@ -19,6 +21,7 @@ impl ScaleLinear {
             domain: Vec::new(),
             range: vec![0, 1],
             tick_count: 10,
+            tick_formatter: None,
         }
     }
 
@@ -33,6 +36,18 @@ impl ScaleLinear {
         &self.domain
     }
 
+    /// Set the approximate amount of ticks to display, e.g. as computed by `fit_tick_count`.
+    pub fn set_tick_count(mut self, tick_count: usize) -> Self {
+        self.tick_count = tick_count;
+        self
+    }
+
+    /// Set the closure that formats the ticks into their labels, instead of their `Display` representation.
+    pub fn set_tick_formatter(mut self, formatter: impl Fn(&f32) -> String + 'static) -> Self {
+        self.tick_formatter = Some(TickFormatter::new(formatter));
+        self
+    }
+
     /// Set the range limits for the scale band.
     pub fn set_range(mut self, range: Vec<isize>) -> Self {
         self.range = range;
@@ -120,6 +135,16 @@ impl Scale<f32> for ScaleLinear {
         self.range[1] as f32
     }
 
+    /// Get the limits of the domain of the scale.
+    fn domain_limits(&self) -> Option<(f32, f32)> {
+        Some((self.domain[0], self.domain[1]))
+    }
+
+    /// Get the scale as a scale of numbers.
+    fn as_numeric(&self) -> Option<&dyn Scale<f32>> {
+        Some(self)
+    }
+
     /// Get the list of ticks that represent the scale on a chart axis.
     fn get_ticks(&self) -> Vec<f32> {
         let mut ticks = Vec::new();
@@ -151,4 +176,81 @@ impl Scale<f32> for ScaleLinear {
 
         ticks
     }
-}
\ No newline at end of file
+
+    /// Get the label of the given tick.
+    fn format_tick(&self, value: &f32) -> String {
+        TickFormatter::format(&self.tick_formatter, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labeled_ticks() {
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![0, 500]);
+
+        let ticks = scale.labeled_ticks();
+
+        assert_eq!(ticks.len(), 11);
+        assert_eq!(ticks[0], (0_f32, String::from("0")));
+        assert_eq!(ticks[5], (250_f32, String::from("50")));
+        assert_eq!(ticks[10], (500_f32, String::from("100")));
+    }
+
+    #[test]
+    fn tick_count_fitted_to_label_width() {
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![0, 400]);
+        let tick_count = scale.fit_tick_count(400, 40);
+        assert_eq!(tick_count, 10);
+
+        let scale = scale.set_tick_count(tick_count);
+        assert_eq!(scale.get_ticks().len(), 11);
+
+        assert_eq!(scale.fit_tick_count(100, 40), 2);
+        assert_eq!(scale.fit_tick_count(30, 40), 0);
+        assert_eq!(scale.fit_tick_count(1600, 40), 40);
+        assert_eq!(scale.fit_tick_count(400, 0), 400);
+    }
+
+    #[test]
+    fn debug_ticks() {
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![500, 0]);
+
+        let table = scale.debug_ticks();
+        let lines = table.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines.len(), scale.get_ticks().len());
+        assert_eq!(lines[0], "0 -> 500");
+        assert_eq!(lines[5], "50 -> 250");
+        assert_eq!(lines[10], "100 -> 0");
+    }
+
+    #[test]
+    fn ticks_formatted_with_si_suffix() {
+        let si_suffix = |value: &f32| match value.abs() {
+            v if v >= 1e6 => format!("{}M", value / 1e6),
+            v if v >= 1e3 => format!("{}k", value / 1e3),
+            _ => value.to_string(),
+        };
+        let scale = ScaleLinear::new()
+            .set_domain(vec![0_f32, 2_000_000_f32])
+            .set_range(vec![0, 500])
+            .set_tick_formatter(si_suffix);
+
+        let labels = scale.labeled_ticks().into_iter().map(|(_, label)| label).collect::<Vec<String>>();
+
+        assert_eq!(scale.format_tick(&2500_f32), "2.5k");
+        assert_eq!(labels[0], "0");
+        assert_eq!(labels[1], "200k");
+        assert_eq!(labels[5], "1M");
+        assert_eq!(labels[10], "2M");
+    }
+}
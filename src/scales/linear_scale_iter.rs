@@ -1,54 +1,155 @@
 use std::ops::{Add, Sub};
 
+/// A domain value that can be iterated over in steps of equal size.
+pub trait StepValue: Copy + PartialOrd + Default + Add<Self, Output = Self> + Sub<Self, Output = Self> {
+    /// The number of whole steps from `start` to the last value that does not pass `end`, and
+    /// whether that last value is `end` itself. `None` when the step leads away from `end`.
+    fn steps_until(start: Self, end: Self, step: Self) -> Option<(usize, bool)>;
+
+    /// The value that is `index` steps away from `start`.
+    fn at_step(start: Self, step: Self, index: usize) -> Self;
+
+    /// The value at `index` of `steps` equal parts of the distance from `start` to `end`.
+    fn between(start: Self, end: Self, index: usize, steps: usize) -> Self;
+}
+
+impl StepValue for f32 {
+    fn steps_until(start: Self, end: Self, step: Self) -> Option<(usize, bool)> {
+        let steps = (end - start) / step;
+        if steps.is_nan() || steps < 0.0 {
+            return None;
+        }
+
+        // Dividing the distance by a rounded step is not exact, so treat a
+        // near-integer amount of steps as landing on the end.
+        let nearest = steps.round();
+        if (steps - nearest).abs() < 1e-3 {
+            Some((nearest as usize, true))
+        } else {
+            Some((steps.floor() as usize, false))
+        }
+    }
+
+    fn at_step(start: Self, step: Self, index: usize) -> Self {
+        start + index as f32 * step
+    }
+
+    fn between(start: Self, end: Self, index: usize, steps: usize) -> Self {
+        start + (end - start) * index as f32 / steps as f32
+    }
+}
+
+impl StepValue for i64 {
+    fn steps_until(start: Self, end: Self, step: Self) -> Option<(usize, bool)> {
+        let distance = end - start;
+        if distance != 0 && distance.signum() != step.signum() {
+            return None;
+        }
+
+        Some(((distance / step) as usize, distance % step == 0))
+    }
+
+    fn at_step(start: Self, step: Self, index: usize) -> Self {
+        start + index as i64 * step
+    }
+
+    fn between(start: Self, end: Self, index: usize, steps: usize) -> Self {
+        start + (end - start) * index as i64 / steps as i64
+    }
+}
+
+/// Iterates from `start` towards `end` in steps of `step`, computing each value from
+/// its index so that rounding errors do not accumulate over long ranges.
 #[derive(Debug)]
 pub struct LinearScaleIter<DR>
 where
-    DR: Copy + PartialOrd + Default + Sub<DR, Output = DR>,
+    DR: StepValue,
 {
     start: DR,
     end: DR,
     step: DR,
-    current: Option<DR>,
-    is_reversed: bool,
+    /// The index of the next value from the front.
+    front: usize,
+    /// The index just past the next value from the back.
+    back: usize,
+    /// The index that yields `end` itself, when the range is a whole number of steps long.
+    /// The values are then divided evenly between `start` and `end`, which avoids the
+    /// rounding error of the step itself.
+    end_index: Option<usize>,
 }
 
 impl<DR> LinearScaleIter<DR>
 where
-    DR: Copy + PartialOrd + Default + Sub<DR, Output = DR>,
+    DR: StepValue,
 {
     pub fn new(start: DR, end: DR, step: DR) -> Self {
         let zero: DR = Default::default();
-        let is_reversed = step < zero;
+        let (count, end_index) = if step == zero {
+            // A step of zero never leaves the start, which is then yielded once.
+            (1, None)
+        } else {
+            match DR::steps_until(start, end, step) {
+                Some((steps, lands_on_end)) => (steps + 1, Some(steps).filter(|_| lands_on_end)),
+                None => (0, None),
+            }
+        };
 
         Self {
             start,
             end,
             step,
-            is_reversed,
-            current: None,
+            front: 0,
+            back: count,
+            end_index,
+        }
+    }
+
+    fn value(&self, index: usize) -> DR {
+        match self.end_index {
+            Some(end_index) if end_index == index => self.end,
+            Some(end_index) => DR::between(self.start, self.end, index, end_index),
+            None => DR::at_step(self.start, self.step, index),
         }
     }
 }
 
 impl<DR> Iterator for LinearScaleIter<DR>
 where
-    DR: Copy + PartialOrd + Default + Add<DR, Output = DR> + Sub<DR, Output = DR>,
+    DR: StepValue,
 {
     type Item = DR;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = if let Some(current) = self.current {
-            current + self.step
-        } else {
-            self.start
-        };
-        self.current =
-            if (self.is_reversed && next >= self.end) || (!self.is_reversed && next <= self.end) {
-                Some(next)
-            } else {
-                None
-            };
-
-        self.current
+        if self.front >= self.back {
+            return None;
+        }
+
+        let value = self.value(self.front);
+        self.front += 1;
+
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+
+        (len, Some(len))
+    }
+}
+
+impl<DR> ExactSizeIterator for LinearScaleIter<DR> where DR: StepValue {}
+
+impl<DR> DoubleEndedIterator for LinearScaleIter<DR>
+where
+    DR: StepValue,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        Some(self.value(self.back))
     }
 }
@@ -0,0 +1,189 @@
+use std::ops::RangeInclusive;
+use crate::scales::{Scale, ScaleType, TickFormatter};
+
+/// The scale to represent data spanning several orders of magnitude.
+#[derive(Debug)]
+pub struct LogScale {
+    /// The domain limits of the dataset that the scale is going to represent.
+    domain: (f32, f32),
+    /// The range limits of the drawable area on the chart.
+    range: (f32, f32),
+    /// The base of the logarithm.
+    base: f32,
+    /// Whether to add ticks between the powers of the base.
+    minor_ticks: bool,
+    /// The closure that formats the ticks into labels, if any.
+    tick_formatter: Option<TickFormatter<f32>>,
+}
+
+impl LogScale {
+    /// Create a new logarithmic scale in base 10 that maps the domain from `start` to `end`
+    /// onto the given range. Both domain limits have to be positive.
+    pub fn new(range: RangeInclusive<f32>, start: f32, end: f32) -> Result<Self, String> {
+        if start <= 0_f32 || end <= 0_f32 || !start.is_finite() || !end.is_finite() {
+            return Err(format!("The domain of a log scale has to be positive, got {} to {}.", start, end));
+        }
+
+        Ok(Self {
+            domain: (start, end),
+            range: (*range.start(), *range.end()),
+            base: 10_f32,
+            minor_ticks: false,
+            tick_formatter: None,
+        })
+    }
+
+    /// Set the base of the logarithm, which has to be positive and different from 1.
+    pub fn set_base(mut self, base: f32) -> Result<Self, String> {
+        if base <= 0_f32 || base == 1_f32 || !base.is_finite() {
+            return Err(format!("The base of a log scale has to be positive and different from 1, got {}.", base));
+        }
+
+        self.base = base;
+        Ok(self)
+    }
+
+    /// Set whether to add ticks at the integer multiples between the powers of the base.
+    pub fn set_minor_ticks(mut self, minor_ticks: bool) -> Self {
+        self.minor_ticks = minor_ticks;
+        self
+    }
+
+    /// Set the closure that formats the ticks into their labels, instead of their `Display` representation.
+    pub fn set_tick_formatter(mut self, formatter: impl Fn(&f32) -> String + 'static) -> Self {
+        self.tick_formatter = Some(TickFormatter::new(formatter));
+        self
+    }
+
+    /// Get the domain limits of the scale.
+    pub fn domain(&self) -> (f32, f32) {
+        self.domain
+    }
+
+    /// Get the base of the logarithm.
+    pub fn base(&self) -> f32 {
+        self.base
+    }
+
+    /// Compute the logarithm of the value in the base of the scale.
+    fn log(&self, value: f32) -> f32 {
+        value.ln() / self.base.ln()
+    }
+}
+
+impl Scale<f32> for LogScale {
+    /// Get the type of the scale.
+    fn get_type(&self) -> ScaleType {
+        ScaleType::Log
+    }
+
+    /// Get the range value for the given domain entry.
+    fn scale(&self, domain: &f32) -> f32 {
+        let log_start = self.log(self.domain.0);
+        let log_end = self.log(self.domain.1);
+        let normalized = if log_start == log_end {
+            0.5
+        } else {
+            (self.log(*domain) - log_start) / (log_end - log_start)
+        };
+
+        self.range.0 + (self.range.1 - self.range.0) * normalized
+    }
+
+    /// Get the bandwidth (if present).
+    fn bandwidth(&self) -> Option<f32> {
+        Some(0_f32)
+    }
+
+    /// Get the start range value.
+    fn range_start(&self) -> f32 {
+        self.range.0
+    }
+
+    /// Get the end range value.
+    fn range_end(&self) -> f32 {
+        self.range.1
+    }
+
+    /// Get the limits of the domain of the scale.
+    fn domain_limits(&self) -> Option<(f32, f32)> {
+        Some(self.domain)
+    }
+
+    /// Get the scale as a scale of numbers.
+    fn as_numeric(&self) -> Option<&dyn Scale<f32>> {
+        Some(self)
+    }
+
+    /// Get the powers of the base within the domain, and the minor ticks between them if enabled.
+    fn get_ticks(&self) -> Vec<f32> {
+        let min = f32::min(self.domain.0, self.domain.1);
+        let max = f32::max(self.domain.0, self.domain.1);
+        let (first_power, last_power) = {
+            let (a, b) = (self.log(min), self.log(max));
+            (f32::min(a, b).floor() as i32, f32::max(a, b).ceil() as i32)
+        };
+        // Allow for the rounding errors of the logarithm when checking the domain limits.
+        let tolerance = (max - min) * 1e-6;
+        let multiples = if self.minor_ticks && self.base > 1_f32 { self.base.ceil() as usize } else { 2 };
+
+        let mut ticks = Vec::new();
+        for power in first_power..=last_power {
+            let magnitude = self.base.powi(power);
+            for multiple in 1..multiples {
+                let tick = magnitude * multiple as f32;
+                if tick >= min - tolerance && tick <= max + tolerance {
+                    ticks.push(tick);
+                }
+            }
+        }
+        ticks.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        ticks
+    }
+
+    /// Get the label of the given tick.
+    fn format_tick(&self, value: &f32) -> String {
+        TickFormatter::format(&self.tick_formatter, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_positive_domain() {
+        assert!(LogScale::new(0.0..=800.0, 0.0, 10000.0).is_err());
+        assert!(LogScale::new(0.0..=800.0, -1.0, 10000.0).is_err());
+        assert!(LogScale::new(0.0..=800.0, 1.0, 10000.0).unwrap().set_base(1.0).is_err());
+    }
+
+    #[test]
+    fn decade_ticks_are_evenly_spaced() {
+        let scale = LogScale::new(0.0..=800.0, 1.0, 10000.0).unwrap();
+
+        assert_eq!(scale.get_ticks(), vec![1.0, 10.0, 100.0, 1000.0, 10000.0]);
+        let positions = scale.get_ticks().iter().map(|tick| scale.scale(tick).round()).collect::<Vec<f32>>();
+        assert_eq!(positions, vec![0.0, 200.0, 400.0, 600.0, 800.0]);
+    }
+
+    #[test]
+    fn minor_ticks_between_decades() {
+        let scale = LogScale::new(0.0..=800.0, 1.0, 100.0).unwrap().set_minor_ticks(true);
+        let ticks = scale.get_ticks();
+
+        assert_eq!(ticks.len(), 19);
+        assert_eq!(ticks[..3], [1.0, 2.0, 3.0]);
+        assert_eq!(ticks[9], 10.0);
+        assert_eq!(ticks[18], 100.0);
+    }
+
+    #[test]
+    fn custom_base() {
+        let scale = LogScale::new(0.0..=300.0, 1.0, 8.0).unwrap().set_base(2.0).unwrap();
+
+        assert_eq!(scale.get_ticks(), vec![1.0, 2.0, 4.0, 8.0]);
+        assert_eq!(scale.scale(&4.0).round(), 200.0);
+    }
+}
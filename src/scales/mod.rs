@@ -1,11 +1,15 @@
 pub mod band;
 pub mod linear;
+pub mod log;
+pub mod pow;
+#[cfg(feature = "chrono")]
+pub mod time;
 
 mod scale_types;
 pub(crate) use scale_types::*;
 
 mod iter_scale;
-pub(crate) use iter_scale::*;
+pub use iter_scale::*;
 
 mod continuous_scale;
 pub use continuous_scale::*;
@@ -16,11 +20,20 @@ pub use discrete_scale::*;
 mod linear_scale_iter;
 pub use linear_scale_iter::*;
 
-#[derive(PartialEq)]
+use std::collections::HashSet;
+use std::fmt;
+
+/// The maximum number of distinct values for which a band scale is inferred.
+const MAX_BAND_CATEGORIES: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ScaleType {
     Band,
     Ordinal,
     Linear,
+    Log,
+    Pow,
+    Time,
 }
 
 /// The Scale trait defines common operations on all scales.
@@ -47,4 +60,138 @@ pub trait Scale<T> {
 
     /// Get the list of ticks that represent the scale on a chart axis.
     fn get_ticks(&self) -> Vec<T>;
+
+    /// Get the limits of the domain of a numeric scale, in the order they are mapped onto the range.
+    /// Scales of categories or instants have no numeric domain.
+    fn domain_limits(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// Get the scale as a scale of numbers, if its domain is numeric, e.g. to place a value
+    /// by the scale of an axis of any domain. Scales of categories or instants have none.
+    fn as_numeric(&self) -> Option<&dyn Scale<f32>> {
+        None
+    }
+
+    /// Get the maximum number of ticks whose labels, of about `avg_label_px` pixels each,
+    /// fit next to each other along an axis of `axis_pixels` without overlapping.
+    /// Labels without a width are treated as a single pixel wide.
+    fn fit_tick_count(&self, axis_pixels: usize, avg_label_px: usize) -> usize {
+        axis_pixels / avg_label_px.max(1)
+    }
+
+    /// Get the label of the given tick, which defaults to its `Display` representation.
+    fn format_tick(&self, value: &T) -> String
+    where
+        T: ToString,
+    {
+        value.to_string()
+    }
+
+    /// Get the ticks paired with their labels, where each tick is represented by
+    /// its position in the range (the middle of the band for scales with a bandwidth).
+    fn labeled_ticks(&self) -> Vec<(f32, String)>
+    where
+        T: ToString,
+    {
+        let bandwidth_offset = match self.get_type() {
+            ScaleType::Band => self.bandwidth().unwrap() / 2_f32,
+            _ => 0_f32,
+        };
+
+        self.get_ticks()
+            .iter()
+            .map(|tick| (self.scale(tick) + bandwidth_offset, self.format_tick(tick)))
+            .collect()
+    }
+
+    /// Get a table with a `value -> pixel` line for each tick, to help debugging the placement of ticks.
+    fn debug_ticks(&self) -> String
+    where
+        T: ToString,
+    {
+        self.labeled_ticks()
+            .iter()
+            .map(|(position, label)| format!("{} -> {}\n", label, position))
+            .collect()
+    }
+}
+
+/// A closure that turns the ticks of a scale into their labels.
+pub(crate) struct TickFormatter<T>(Box<dyn Fn(&T) -> String>);
+
+impl<T> TickFormatter<T> {
+    pub(crate) fn new(formatter: impl Fn(&T) -> String + 'static) -> Self {
+        Self(Box::new(formatter))
+    }
+
+    /// Format the given tick, or fall back to its `Display` representation without a formatter.
+    pub(crate) fn format(formatter: &Option<Self>, value: &T) -> String
+    where
+        T: ToString,
+    {
+        match formatter {
+            Some(formatter) => (formatter.0)(value),
+            None => value.to_string(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for TickFormatter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TickFormatter")
+    }
+}
+
+/// Infer the type of scale that best represents the given sample data.
+/// Numeric samples map to a linear scale, unless they consist of a few repeated
+/// whole numbers, in which case they are treated as categories. Non-numeric samples
+/// map to a band scale, or to an ordinal scale when there are too many distinct
+/// values to fit in bands.
+pub fn infer_scale_type<T: ToString>(samples: &[T]) -> ScaleType {
+    let labels: Vec<String> = samples.iter().map(|sample| sample.to_string()).collect();
+    let distinct: HashSet<&str> = labels.iter().map(|label| label.trim()).collect();
+    let numbers: Vec<f64> = labels.iter().filter_map(|label| label.trim().parse::<f64>().ok()).collect();
+    let is_numeric = !labels.is_empty() && numbers.len() == labels.len() && numbers.iter().all(|n| n.is_finite());
+
+    if is_numeric {
+        let is_whole = numbers.iter().all(|n| n.fract() == 0_f64);
+        let is_repeated = distinct.len() < labels.len();
+
+        if is_whole && is_repeated && distinct.len() <= MAX_BAND_CATEGORIES {
+            ScaleType::Band
+        } else {
+            ScaleType::Linear
+        }
+    } else if distinct.len() <= MAX_BAND_CATEGORIES {
+        ScaleType::Band
+    } else {
+        ScaleType::Ordinal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_linear_for_continuous_numbers() {
+        assert_eq!(infer_scale_type(&[0.5_f32, 1.25, 3.75, 2.0, 9.5]), ScaleType::Linear);
+        assert_eq!(infer_scale_type(&[1, 2, 3, 4, 5]), ScaleType::Linear);
+        let many: Vec<i32> = (0..100).map(|i| i % 50).collect();
+        assert_eq!(infer_scale_type(&many), ScaleType::Linear);
+    }
+
+    #[test]
+    fn infer_band_for_few_categories() {
+        assert_eq!(infer_scale_type(&["A", "B", "A", "C", "B"]), ScaleType::Band);
+        assert_eq!(infer_scale_type(&[2019, 2020, 2019, 2020]), ScaleType::Band);
+        assert_eq!(infer_scale_type::<String>(&[]), ScaleType::Band);
+    }
+
+    #[test]
+    fn infer_ordinal_for_many_categories() {
+        let names: Vec<String> = (0..20).map(|i| format!("item {}", i)).collect();
+        assert_eq!(infer_scale_type(&names), ScaleType::Ordinal);
+    }
 }
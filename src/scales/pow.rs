@@ -0,0 +1,159 @@
+use crate::scales::{Scale, ScaleType, TickFormatter};
+use crate::scales::linear::ScaleLinear;
+
+/// The scale to represent data through a power transform, such as a square root
+/// to make the area of markers proportional to their value.
+#[derive(Debug)]
+pub struct PowScale {
+    /// The domain limits of the dataset that the scale is going to represent.
+    domain: Vec<f32>,
+    /// The range limits of the drawable area on the chart.
+    range: Vec<isize>,
+    /// The exponent applied to the domain values.
+    exponent: f32,
+    /// The closure that formats the ticks into labels, if any.
+    tick_formatter: Option<TickFormatter<f32>>,
+}
+
+impl PowScale {
+    /// Create a new power scale with the given exponent, mapping the domain onto the range.
+    pub fn new(exponent: f32, domain: Vec<f32>, range: Vec<isize>) -> Self {
+        Self {
+            domain,
+            range,
+            exponent,
+            tick_formatter: None,
+        }
+    }
+
+    /// Create a new square root scale, mapping the domain onto the range.
+    pub fn sqrt(domain: Vec<f32>, range: Vec<isize>) -> Self {
+        Self::new(0.5, domain, range)
+    }
+
+    /// Set the exponent applied to the domain values.
+    pub fn set_exponent(mut self, exponent: f32) -> Self {
+        self.exponent = exponent;
+        self
+    }
+
+    /// Set the closure that formats the ticks into their labels, instead of their `Display` representation.
+    pub fn set_tick_formatter(mut self, formatter: impl Fn(&f32) -> String + 'static) -> Self {
+        self.tick_formatter = Some(TickFormatter::new(formatter));
+        self
+    }
+
+    /// Get the exponent applied to the domain values.
+    pub fn exponent(&self) -> f32 {
+        self.exponent
+    }
+
+    /// Get the domain limits of the scale.
+    pub fn domain(&self) -> &Vec<f32> {
+        &self.domain
+    }
+
+    /// Get the range limits of the scale.
+    pub fn range(&self) -> &Vec<isize> {
+        &self.range
+    }
+
+    /// Raise the magnitude of the value to the exponent, keeping its sign so that
+    /// negative values are handled symmetrically.
+    fn transform(&self, value: f32) -> f32 {
+        value.signum() * value.abs().powf(self.exponent)
+    }
+}
+
+impl Scale<f32> for PowScale {
+    /// Get the type of the scale.
+    fn get_type(&self) -> ScaleType {
+        ScaleType::Pow
+    }
+
+    /// Get the range value for the given domain entry.
+    fn scale(&self, domain: &f32) -> f32 {
+        let a = self.transform(self.domain[0]);
+        let b = self.transform(self.domain[1]);
+        let normalized = if a == b {
+            0.5
+        } else {
+            (self.transform(*domain) - a) / (b - a)
+        };
+        let start = self.range[0] as f32;
+        let end = self.range[1] as f32;
+
+        (end - start) * normalized + start
+    }
+
+    /// Get the bandwidth (if present).
+    fn bandwidth(&self) -> Option<f32> {
+        Some(0_f32)
+    }
+
+    /// Get the start range value.
+    fn range_start(&self) -> f32 {
+        self.range[0] as f32
+    }
+
+    /// Get the end range value.
+    fn range_end(&self) -> f32 {
+        self.range[1] as f32
+    }
+
+    /// Get the limits of the domain of the scale.
+    fn domain_limits(&self) -> Option<(f32, f32)> {
+        Some((self.domain[0], self.domain[1]))
+    }
+
+    /// Get the scale as a scale of numbers.
+    fn as_numeric(&self) -> Option<&dyn Scale<f32>> {
+        Some(self)
+    }
+
+    /// Get the list of ticks that represent the scale on a chart axis.
+    /// The ticks are the same as the ones of a linear scale over the same domain.
+    fn get_ticks(&self) -> Vec<f32> {
+        ScaleLinear::new()
+            .set_domain(self.domain.clone())
+            .set_range(self.range.clone())
+            .get_ticks()
+    }
+
+    /// Get the label of the given tick.
+    fn format_tick(&self, value: &f32) -> String {
+        TickFormatter::format(&self.tick_formatter, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_of_four_times_the_value_doubles_the_distance() {
+        let scale = PowScale::sqrt(vec![0_f32, 100_f32], vec![0, 20]);
+
+        assert_eq!(scale.scale(&0_f32), 0_f32);
+        assert_eq!(scale.scale(&4_f32), 2_f32 * scale.scale(&1_f32));
+        assert_eq!(scale.scale(&36_f32), 2_f32 * scale.scale(&9_f32));
+        assert_eq!(scale.scale(&100_f32), 20_f32);
+    }
+
+    #[test]
+    fn negative_domain_is_symmetric() {
+        let scale = PowScale::sqrt(vec![-100_f32, 100_f32], vec![0, 200]);
+
+        assert_eq!(scale.scale(&0_f32), 100_f32);
+        assert_eq!(scale.scale(&-25_f32), 200_f32 - scale.scale(&25_f32));
+        assert_eq!(scale.scale(&25_f32), 150_f32);
+    }
+
+    #[test]
+    fn custom_exponent() {
+        let scale = PowScale::new(2_f32, vec![0_f32, 10_f32], vec![0, 100]);
+
+        assert_eq!(scale.scale(&5_f32), 25_f32);
+        assert_eq!(scale.set_exponent(1_f32).scale(&5_f32), 50_f32);
+    }
+}
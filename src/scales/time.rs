@@ -0,0 +1,298 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use crate::scales::{Scale, ScaleType, TickFormatter};
+
+/// The approximate number of ticks to display.
+const TARGET_TICK_COUNT: i64 = 10;
+
+/// The intervals between ticks that are aligned to a fixed amount of seconds, in seconds.
+const FIXED_INTERVALS: [i64; 15] = [
+    1, 5, 15, 30,
+    60, 5 * 60, 15 * 60, 30 * 60,
+    3600, 3 * 3600, 6 * 3600, 12 * 3600,
+    86400, 2 * 86400, 7 * 86400,
+];
+
+/// The intervals between ticks that are aligned to the start of a month, in months.
+const MONTH_INTERVALS: [i64; 3] = [1, 3, 6];
+
+/// The distance between two ticks of a time scale.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum TickInterval {
+    Seconds(i64),
+    Months(i64),
+}
+
+/// The scale to represent instants in time.
+#[derive(Debug)]
+pub struct TimeScale {
+    /// The domain limits of the time span that the scale is going to represent.
+    domain: (DateTime<Utc>, DateTime<Utc>),
+    /// The range limits of the drawable area on the chart.
+    range: Vec<isize>,
+    /// The closure that formats the ticks into labels, if any.
+    tick_formatter: Option<TickFormatter<DateTime<Utc>>>,
+}
+
+impl TimeScale {
+    /// Create a new time scale that maps the instants from `start` to `end` onto `0..dimension` pixels.
+    pub fn new(dimension: isize, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            domain: (start, end),
+            range: vec![0, dimension],
+            tick_formatter: None,
+        }
+    }
+
+    /// Set the range limits for the scale.
+    pub fn set_range(mut self, range: Vec<isize>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Set the closure that formats the ticks into their labels, instead of their `Display` representation.
+    pub fn set_tick_formatter(mut self, formatter: impl Fn(&DateTime<Utc>) -> String + 'static) -> Self {
+        self.tick_formatter = Some(TickFormatter::new(formatter));
+        self
+    }
+
+    /// Get the domain limits of the scale.
+    pub fn domain(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        self.domain
+    }
+
+    /// Choose the smallest human-friendly interval that yields no more than the target amount of ticks.
+    fn tick_interval(span_seconds: i64) -> TickInterval {
+        let month_seconds = 30 * 86400;
+
+        if let Some(seconds) = FIXED_INTERVALS.iter().find(|seconds| span_seconds / *seconds <= TARGET_TICK_COUNT) {
+            return TickInterval::Seconds(*seconds);
+        }
+        if let Some(months) = MONTH_INTERVALS.iter().find(|months| span_seconds / (*months * month_seconds) <= TARGET_TICK_COUNT) {
+            return TickInterval::Months(*months);
+        }
+
+        // Use a whole number of years of the form 1, 2 or 5 times a power of 10.
+        let mut years = 1;
+        loop {
+            for multiplier in [1, 2, 5].iter() {
+                if span_seconds / (years * multiplier * 12 * month_seconds) <= TARGET_TICK_COUNT {
+                    return TickInterval::Months(years * multiplier * 12);
+                }
+            }
+            years *= 10;
+        }
+    }
+
+    /// The domain limits in ascending order.
+    fn ordered_domain(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        if self.domain.0 <= self.domain.1 {
+            self.domain
+        } else {
+            (self.domain.1, self.domain.0)
+        }
+    }
+
+    /// Choose the format of the tick labels from the interval between the ticks,
+    /// showing only the part of the instant that changes from one tick to the next.
+    fn default_tick_format(&self) -> &'static str {
+        let (start, end) = self.ordered_domain();
+
+        match Self::tick_interval((end - start).num_seconds()) {
+            TickInterval::Months(months) if months % 12 == 0 => "%Y",
+            TickInterval::Months(_) => "%b",
+            TickInterval::Seconds(seconds) if seconds >= 86400 => "%d",
+            TickInterval::Seconds(seconds) if seconds >= 60 => "%H:%M",
+            TickInterval::Seconds(_) => "%H:%M:%S",
+        }
+    }
+
+    /// Generate the ticks aligned to a multiple of the given amount of seconds since the epoch.
+    fn fixed_ticks(start: DateTime<Utc>, end: DateTime<Utc>, seconds: i64) -> Vec<DateTime<Utc>> {
+        let first = (start.timestamp() + seconds - 1).div_euclid(seconds) * seconds;
+        let mut tick = Utc.timestamp_opt(first, 0).unwrap();
+        // Ticks of a whole second might fall before the start if it has a fractional part.
+        if tick < start {
+            tick += Duration::seconds(seconds);
+        }
+
+        let mut ticks = Vec::new();
+        while tick <= end {
+            ticks.push(tick);
+            tick += Duration::seconds(seconds);
+        }
+
+        ticks
+    }
+
+    /// Generate the ticks at the start of every given amount of months, aligned to the start of the year.
+    fn month_ticks(start: DateTime<Utc>, end: DateTime<Utc>, months: i64) -> Vec<DateTime<Utc>> {
+        let start_month = start.year() as i64 * 12 + start.month0() as i64;
+        let mut month = start_month.div_euclid(months) * months;
+
+        let mut ticks = Vec::new();
+        while let Some(tick) = Utc.with_ymd_and_hms(month.div_euclid(12) as i32, month.rem_euclid(12) as u32 + 1, 1, 0, 0, 0).single() {
+            if tick > end {
+                break;
+            }
+            if tick >= start {
+                ticks.push(tick);
+            }
+            month += months;
+        }
+
+        ticks
+    }
+}
+
+impl Scale<DateTime<Utc>> for TimeScale {
+    /// Get the type of the scale.
+    fn get_type(&self) -> ScaleType {
+        ScaleType::Time
+    }
+
+    /// Get the range value for the given domain entry.
+    fn scale(&self, domain: &DateTime<Utc>) -> f32 {
+        let span = (self.domain.1 - self.domain.0).num_milliseconds();
+        let normalized = if span == 0 {
+            0.5
+        } else {
+            (*domain - self.domain.0).num_milliseconds() as f64 / span as f64
+        };
+        let a = self.range[0] as f64;
+        let b = self.range[1] as f64;
+
+        ((b - a) * normalized + a) as f32
+    }
+
+    /// Get the bandwidth (if present).
+    fn bandwidth(&self) -> Option<f32> {
+        Some(0_f32)
+    }
+
+    /// Get the start range value.
+    fn range_start(&self) -> f32 {
+        self.range[0] as f32
+    }
+
+    /// Get the end range value.
+    fn range_end(&self) -> f32 {
+        self.range[1] as f32
+    }
+
+    /// Get the list of ticks at a human-friendly interval that represent the scale on a chart axis.
+    fn get_ticks(&self) -> Vec<DateTime<Utc>> {
+        let (start, end) = self.ordered_domain();
+        let span_seconds = (end - start).num_seconds();
+
+        let ticks = match Self::tick_interval(span_seconds) {
+            TickInterval::Seconds(seconds) => Self::fixed_ticks(start, end, seconds),
+            TickInterval::Months(months) => Self::month_ticks(start, end, months),
+        };
+
+        // A span too short to contain any aligned instant is represented by its start.
+        if ticks.is_empty() {
+            vec![start]
+        } else {
+            ticks
+        }
+    }
+
+    /// Get the label of the given tick, in a format that suits the interval between the ticks
+    /// unless a tick formatter is set.
+    fn format_tick(&self, value: &DateTime<Utc>) -> String {
+        match self.tick_formatter {
+            Some(_) => TickFormatter::format(&self.tick_formatter, value),
+            None => value.format(self.default_tick_format()).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn scale_interpolates_between_instants() {
+        let scale = TimeScale::new(800, instant(2024, 1, 1, 0, 0), instant(2024, 1, 2, 0, 0));
+
+        assert_eq!(scale.scale(&instant(2024, 1, 1, 0, 0)), 0_f32);
+        assert_eq!(scale.scale(&instant(2024, 1, 1, 12, 0)), 400_f32);
+        assert_eq!(scale.scale(&instant(2024, 1, 2, 0, 0)), 800_f32);
+    }
+
+    #[test]
+    fn hourly_ticks_for_a_day() {
+        let scale = TimeScale::new(800, instant(2024, 1, 1, 0, 0), instant(2024, 1, 2, 0, 0));
+        let ticks = scale.get_ticks();
+
+        assert_eq!(ticks.len(), 9);
+        assert_eq!(ticks[0], instant(2024, 1, 1, 0, 0));
+        assert_eq!(ticks[1], instant(2024, 1, 1, 3, 0));
+    }
+
+    #[test]
+    fn minute_ticks_are_aligned() {
+        let scale = TimeScale::new(800, instant(2024, 3, 31, 0, 52), instant(2024, 3, 31, 1, 37));
+        let ticks = scale.get_ticks();
+
+        assert_eq!(ticks[0], instant(2024, 3, 31, 0, 55));
+        assert_eq!(ticks[1], instant(2024, 3, 31, 1, 0));
+        assert_eq!(ticks.len(), 9);
+    }
+
+    #[test]
+    fn month_ticks_cross_month_and_year_boundaries() {
+        let scale = TimeScale::new(800, instant(2023, 10, 14, 6, 0), instant(2024, 5, 20, 0, 0));
+        let ticks = scale.get_ticks();
+
+        assert_eq!(ticks.first(), Some(&instant(2023, 11, 1, 0, 0)));
+        assert_eq!(ticks.last(), Some(&instant(2024, 5, 1, 0, 0)));
+        assert_eq!(ticks.len(), 7);
+        assert!(ticks.iter().all(|tick| tick.day() == 1));
+    }
+
+    #[test]
+    fn year_ticks_for_decades() {
+        let scale = TimeScale::new(800, instant(1990, 6, 1, 0, 0), instant(2024, 1, 1, 0, 0));
+        let ticks = scale.get_ticks();
+
+        assert_eq!(ticks.first(), Some(&instant(1995, 1, 1, 0, 0)));
+        assert!(ticks.iter().all(|tick| tick.month() == 1 && tick.year() % 5 == 0));
+    }
+
+    #[test]
+    fn zero_length_span_has_single_tick() {
+        let moment = Utc.with_ymd_and_hms(2024, 1, 1, 10, 30, 0).unwrap() + Duration::milliseconds(250);
+        let scale = TimeScale::new(800, moment, moment);
+
+        assert_eq!(scale.get_ticks(), vec![moment]);
+        assert_eq!(scale.scale(&moment), 400_f32);
+    }
+
+    #[test]
+    fn month_labels_are_abbreviated() {
+        let scale = TimeScale::new(800, instant(2023, 10, 14, 6, 0), instant(2024, 5, 20, 0, 0));
+        let labels = scale.labeled_ticks().into_iter().map(|(_, label)| label).collect::<Vec<String>>();
+
+        assert_eq!(labels, vec!["Nov", "Dec", "Jan", "Feb", "Mar", "Apr", "May"]);
+    }
+
+    #[test]
+    fn year_and_hour_labels() {
+        let decades = TimeScale::new(800, instant(1990, 6, 1, 0, 0), instant(2024, 1, 1, 0, 0));
+        assert_eq!(decades.format_tick(&instant(1995, 1, 1, 0, 0)), "1995");
+
+        let day = TimeScale::new(800, instant(2024, 1, 1, 0, 0), instant(2024, 1, 2, 0, 0));
+        assert_eq!(day.format_tick(&instant(2024, 1, 1, 3, 0)), "03:00");
+
+        let month = TimeScale::new(800, instant(2024, 2, 1, 0, 0), instant(2024, 3, 1, 0, 0));
+        assert_eq!(month.format_tick(&instant(2024, 2, 8, 0, 0)), "08");
+
+        let formatted = day.set_tick_formatter(|tick| tick.format("%H h").to_string());
+        assert_eq!(formatted.format_tick(&instant(2024, 1, 1, 3, 0)), "03 h");
+    }
+}
@@ -0,0 +1,107 @@
+/// Compute the `q`-th quantile (between 0 and 1) of an already sorted slice.
+///
+/// The value is linearly interpolated between the two closest ranks, the same way
+/// D3's `quantileSorted` does. The caller is responsible for sorting the samples in
+/// ascending order; an empty slice yields `NaN`.
+pub fn quantile(sorted: &[f32], q: f32) -> f32 {
+    if sorted.is_empty() {
+        return f32::NAN;
+    }
+
+    let q = q.clamp(0_f32, 1_f32);
+    let position = (sorted.len() - 1) as f32 * q;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f32;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+/// The coefficients of the straight line `y = slope * x + intercept`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LinearFit {
+    pub slope: f32,
+    pub intercept: f32,
+}
+
+impl LinearFit {
+    /// Evaluate the line at the given `x`.
+    pub fn at(&self, x: f32) -> f32 {
+        self.slope * x + self.intercept
+    }
+}
+
+/// Fit a straight line through the `(x, y)` points with the least squares method.
+///
+/// Returns `None` when there are fewer than two points or when all points share the
+/// same `x`, since no single line fits those.
+pub fn linear_regression(points: &[(f32, f32)]) -> Option<LinearFit> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| *x as f64).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| *y as f64).sum::<f64>() / n;
+    let covariance = points.iter().map(|(x, y)| (*x as f64 - mean_x) * (*y as f64 - mean_y)).sum::<f64>();
+    let variance = points.iter().map(|(x, _)| (*x as f64 - mean_x).powi(2)).sum::<f64>();
+
+    if variance == 0_f64 {
+        return None;
+    }
+
+    let slope = covariance / variance;
+
+    Some(LinearFit {
+        slope: slope as f32,
+        intercept: (mean_y - slope * mean_x) as f32,
+    })
+}
+
+#[test]
+fn median_of_even_number_of_samples() {
+    assert_eq!(quantile(&[1_f32, 2_f32, 3_f32, 4_f32], 0.5), 2.5);
+}
+
+#[test]
+fn quartiles() {
+    let sorted = [1_f32, 2_f32, 3_f32, 4_f32, 5_f32, 6_f32, 7_f32, 8_f32, 9_f32];
+
+    assert_eq!(quantile(&sorted, 0_f32), 1_f32);
+    assert_eq!(quantile(&sorted, 0.25), 3_f32);
+    assert_eq!(quantile(&sorted, 0.5), 5_f32);
+    assert_eq!(quantile(&sorted, 0.75), 7_f32);
+    assert_eq!(quantile(&sorted, 1_f32), 9_f32);
+}
+
+#[test]
+fn interpolated_quartiles() {
+    let sorted = [3_f32, 6_f32, 7_f32, 8_f32, 8_f32, 10_f32, 13_f32, 15_f32, 16_f32, 20_f32];
+
+    assert_eq!(quantile(&sorted, 0_f32), 3_f32);
+    assert_eq!(quantile(&sorted, 0.25), 7.25);
+    assert_eq!(quantile(&sorted, 0.5), 9_f32);
+    assert_eq!(quantile(&sorted, 0.75), 14.5);
+    assert_eq!(quantile(&sorted, 1_f32), 20_f32);
+}
+
+#[test]
+fn single_sample_and_empty_input() {
+    assert_eq!(quantile(&[42_f32], 0.3), 42_f32);
+    assert!(quantile(&[], 0.5).is_nan());
+}
+
+#[test]
+fn regression_of_known_points() {
+    let fit = linear_regression(&[(1_f32, 2_f32), (2_f32, 4_f32), (3_f32, 5_f32), (4_f32, 4_f32), (5_f32, 5_f32)]).unwrap();
+
+    assert!((fit.slope - 0.6).abs() < 1e-6);
+    assert!((fit.intercept - 2.2).abs() < 1e-6);
+    assert!((fit.at(10_f32) - 8.2).abs() < 1e-5);
+}
+
+#[test]
+fn regression_needs_distinct_x() {
+    assert_eq!(linear_regression(&[(1_f32, 2_f32)]), None);
+    assert_eq!(linear_regression(&[(1_f32, 2_f32), (1_f32, 3_f32)]), None);
+}
@@ -0,0 +1,132 @@
+use svg::node::Node;
+use svg::node::element::Group;
+use crate::components::annotation::Annotation;
+use crate::Scale;
+use crate::scales::ScaleType;
+use crate::components::DatumRepresentation;
+use crate::views::{view_class, View};
+use crate::components::legend::LegendEntry;
+use crate::ChartError;
+
+/// A View that places callouts, e.g. "record high", at a value of a category.
+pub struct AnnotationView<'a> {
+    leader_line_length: Option<f32>,
+    entries: Vec<Annotation>,
+    x_scale: Option<&'a dyn Scale<String>>,
+    y_scale: Option<&'a dyn Scale<f32>>,
+    class: Option<String>,
+}
+
+impl<'a> AnnotationView<'a> {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            leader_line_length: None,
+            entries: Vec::new(),
+            x_scale: None,
+            y_scale: None,
+            class: None,
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &'a impl Scale<String>) -> Self {
+        self.x_scale = Some(scale);
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
+        self.y_scale = Some(scale);
+        self
+    }
+
+    /// Set the length of the line between the marker and the text of the annotations
+    /// that are added after this.
+    pub fn set_leader_line_length(mut self, length: f32) -> Self {
+        self.leader_line_length = Some(length);
+        self
+    }
+
+    /// Add an annotation at the middle of the band of the category, at the height of the value.
+    pub fn add_annotation(self, category: &str, value: f32, text: &str) -> Result<Self, ChartError> {
+        let x_scale = match self.x_scale {
+            Some(scale) if scale.get_type() == ScaleType::Band => scale,
+            None => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the X scale should be a band scale"))),
+        };
+        let y_scale = match self.y_scale {
+            Some(scale) => scale,
+            None => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
+        };
+
+        let category = category.to_string();
+        if !x_scale.get_ticks().contains(&category) {
+            return Err(ChartError::InvalidScale(format!("the category {} is not in the domain of the X scale", category)));
+        }
+
+        let x = x_scale.scale(&category) + x_scale.bandwidth().unwrap() / 2_f32;
+        let leader_line_length = self.leader_line_length;
+
+        Ok(self.add_annotation_at(x, y_scale.scale(&value), text, leader_line_length))
+    }
+
+    /// Add an annotation at a position in the view that is already resolved, e.g. by the chart.
+    pub(crate) fn add_annotation_at(mut self, x: f32, y: f32, text: &str, leader_line_length: Option<f32>) -> Self {
+        self.entries.push(Annotation::new(x, y, text.to_string(), leader_line_length));
+        self
+    }
+}
+
+impl<'a> Default for AnnotationView<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> View<'a> for AnnotationView<'a> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new().set("class", view_class("annotation-view", &self.class));
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        Ok(group)
+    }
+
+    /// Annotations are not represented in the legend.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScaleBand, ScaleLinear};
+
+    #[test]
+    fn annotation_needs_the_category_in_a_band_scale() {
+        let x = ScaleBand::new()
+            .set_domain(vec![String::from("1977"), String::from("1978")])
+            .set_range(vec![0, 600]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 400_000_000_f32])
+            .set_range(vec![400, 0]);
+
+        let view = AnnotationView::new().set_x_scale(&x).set_y_scale(&y);
+        assert!(view.add_annotation("2000", 0_f32, "Missing").is_err());
+
+        let result = AnnotationView::new().set_x_scale(&x).add_annotation("1978", 0_f32, "Record high");
+        assert!(matches!(result.err(), Some(ChartError::MissingDimension(_))));
+    }
+}
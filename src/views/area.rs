@@ -1,25 +1,35 @@
+use std::collections::HashMap;
 use svg::node::Node;
 use svg::node::element::Group;
 use crate::components::scatter::{ScatterPoint, MarkerType, PointLabelPosition};
 use crate::colors::Color;
 use crate::Scale;
 use crate::views::datum::PointDatum;
-use crate::views::View;
+use crate::views::{clip_to_plot_area, view_class, View};
 use crate::components::DatumRepresentation;
 use std::fmt::Display;
 use crate::components::legend::{LegendEntry, LegendMarkerType};
-use crate::components::area::AreaSeries;
+use crate::components::area::{AreaSeries, Curve};
+use crate::components::stack::StackOffset;
+use crate::ChartError;
+use crate::components::IdScope;
 
 /// A View that represents data as a scatter plot.
 pub struct AreaSeriesView<'a, T: Display + Clone, U: Display + Clone> {
     labels_visible: bool,
+    clip_to_plot_area: bool,
     label_position: PointLabelPosition,
     marker_type: MarkerType,
+    curve: Curve,
     entries: Vec<AreaSeries<T, U>>,
     colors: Vec<Color>,
+    keys: Vec<String>,
+    color_map: HashMap<String, String>,
+    stack_offset: StackOffset,
     x_scale: Option<&'a dyn Scale<T>>,
     y_scale: Option<&'a dyn Scale<U>>,
     custom_data_label: String,
+    class: Option<String>,
 }
 
 impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
@@ -27,13 +37,19 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
     pub fn new() -> Self {
         Self {
             labels_visible: true,
+            clip_to_plot_area: false,
             label_position: PointLabelPosition::NW,
             marker_type: MarkerType::Circle,
+            curve: Curve::Linear,
             entries: Vec::new(),
             colors: Color::color_scheme_10(),
+            keys: Vec::new(),
+            color_map: HashMap::new(),
+            stack_offset: StackOffset::Zero,
             x_scale: None,
             y_scale: None,
             custom_data_label: String::new(),
+            class: None,
         }
     }
 
@@ -49,6 +65,19 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
         self
     }
 
+    /// Set the keys of the layers in case of a stacked area chart, from the bottom to the top.
+    pub fn set_keys(mut self, keys: Vec<String>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Set the way in which the baseline of a stacked area chart is computed, e.g. to
+    /// draw a streamgraph that wiggles around the middle of the chart.
+    pub fn set_stack_offset(mut self, stack_offset: StackOffset) -> Self {
+        self.stack_offset = stack_offset;
+        self
+    }
+
     /// Set the positioning of the labels.
     pub fn set_label_position(mut self, label_position: PointLabelPosition) -> Self {
         self.label_position = label_position;
@@ -61,6 +90,12 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
         self
     }
 
+    /// Set the way in which the top of the area connects the points, e.g. in steps.
+    pub fn set_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
     /// Set the color palette of the view.
     pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
         self.colors = colors;
@@ -73,6 +108,13 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
         self
     }
 
+    /// Set whether the view should be clipped to the plot area, so that
+    /// points which fall outside of the scales' domains are not rendered.
+    pub fn set_clip(mut self, clip: bool) -> Self {
+        self.clip_to_plot_area = clip;
+        self
+    }
+
     /// Set custom label for the dataset.
     /// This will work when the dataset represents only a single
     /// type of data (i.e. there are no different "keys" by which to
@@ -83,31 +125,20 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
     }
 
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, String> {
+    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, ChartError> {
         match self.x_scale {
             Some(_) => {},
-            _ => return Err("Please provide a scale for the X dimension before loading data".to_string()),
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
         }
         match self.y_scale {
             Some(_) => {},
-            _ => return Err("Please provide a scale for the Y dimension before loading data".to_string()),
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
         }
 
         // Compute corresponding offsets to apply in case there is a non-zero bandwidth.
-        let y_bandwidth_offset = {
-            if self.y_scale.unwrap().is_range_reversed() {
-                -self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
-            } else {
-                self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
-            }
-        };
-        let x_bandwidth_offset = {
-            if self.x_scale.unwrap().is_range_reversed() {
-                -self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
-            } else {
-                self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
-            }
-        };
+        // Bands always start at their lowest range value, even when the scale is reversed.
+        let y_bandwidth_offset = self.y_scale.unwrap().bandwidth().unwrap() / 2_f32;
+        let x_bandwidth_offset = self.x_scale.unwrap().bandwidth().unwrap() / 2_f32;
 
         let mut points = data.iter().map(|datum| {
             let scaled_x = self.x_scale.unwrap().scale(&datum.get_x());
@@ -127,22 +158,109 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
         points.push(ScatterPoint::new(self.x_scale.unwrap().scale(&last.get_x()) + x_bandwidth_offset, y_origin, self.marker_type, 5, data[0].get_x(), data[0].get_y(), self.label_position, false, false, "#fff".to_string()));
         points.push(ScatterPoint::new(self.x_scale.unwrap().scale(&first.get_x()) + x_bandwidth_offset, y_origin, self.marker_type, 5, data[0].get_x(), data[0].get_y(), self.label_position, false, false, "#fff".to_string()));
 
-        self.entries.push(AreaSeries::new(points, self.colors[0].as_hex()));
+        self.entries.push(AreaSeries::new(points, self.colors[0].as_hex()).set_curve(self.curve));
+
+        Ok(self)
+    }
+}
+
+impl<'a, T: Display + Clone> AreaSeriesView<'a, T, f32> {
+    /// Load and process a dataset of PointDatum points with several keys, which are stacked
+    /// in layers on the baseline of the stack offset. A key without a value at an X value
+    /// has a layer of no thickness there.
+    pub fn load_stacked_data(mut self, data: &[impl PointDatum<T, f32>]) -> Result<Self, ChartError> {
+        match self.x_scale {
+            Some(_) => {},
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
+        }
+        match self.y_scale {
+            Some(_) => {},
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
+        }
+
+        // If no keys were explicitly provided, extract the keys from the data.
+        if self.keys.is_empty() {
+            for datum in data.iter() {
+                if !self.keys.contains(&datum.get_key()) {
+                    self.keys.push(datum.get_key());
+                }
+            }
+        }
+
+        for (i, key) in self.keys.iter().enumerate() {
+            self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
+        }
+
+        // The X values in the order of the data, which are identified by their labels.
+        let mut x_values: Vec<T> = Vec::new();
+        let mut x_indices = HashMap::new();
+        for datum in data.iter() {
+            x_indices.entry(datum.get_x().to_string()).or_insert_with(|| {
+                x_values.push(datum.get_x());
+                x_values.len() - 1
+            });
+        }
+
+        let mut layers = vec![vec![0_f32; x_values.len()]; self.keys.len()];
+        for datum in data.iter() {
+            if let Some(layer) = self.keys.iter().position(|key| *key == datum.get_key()) {
+                layers[layer][x_indices[&datum.get_x().to_string()]] += datum.get_y();
+            }
+        }
+
+        let x_scale = self.x_scale.unwrap();
+        let y_scale = self.y_scale.unwrap();
+        let x_bandwidth_offset = x_scale.bandwidth().unwrap() / 2_f32;
+        let y_bandwidth_offset = y_scale.bandwidth().unwrap() / 2_f32;
+
+        for (key, (layer, boundaries)) in self.keys.iter().zip(layers.iter().zip(self.stack_offset.stack(&layers))) {
+            let color = self.color_map.get(key).unwrap().clone();
+            let points = x_values.iter().zip(layer.iter().zip(boundaries.iter())).map(|(x, (value, (_, upper)))| {
+                ScatterPoint::new(x_scale.scale(x) + x_bandwidth_offset, y_scale.scale(upper) + y_bandwidth_offset, self.marker_type, 5, x.clone(), *value, self.label_position, self.labels_visible, true, color.clone())
+            }).collect::<Vec<ScatterPoint<T, f32>>>();
+            let baseline = x_values.iter().zip(boundaries.iter()).map(|(x, (lower, _))| {
+                (x_scale.scale(x) + x_bandwidth_offset, y_scale.scale(lower) + y_bandwidth_offset)
+            }).collect::<Vec<(f32, f32)>>();
+
+            // A key without data has no area to draw, but keeps its entry in the legend.
+            if points.is_empty() {
+                continue;
+            }
+
+            self.entries.push(AreaSeries::new(points, color).set_curve(self.curve).set_baseline(baseline));
+        }
 
         Ok(self)
     }
 }
 
 impl<'a, T: Display + Clone, U: Display + Clone> View<'a> for AreaSeriesView<'a, T, U> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new();
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let _ids = IdScope::enter();
+        let mut group = Group::new().set("class", view_class("area-view", &self.class));
 
         for entry in self.entries.iter() {
             let child_svg = entry.to_svg()?;
             group.append(child_svg);
         }
 
+        if self.clip_to_plot_area {
+            if let (Some(x_scale), Some(y_scale)) = (self.x_scale, self.y_scale) {
+                group = clip_to_plot_area(
+                    group,
+                    (x_scale.range_start(), x_scale.range_end()),
+                    (y_scale.range_start(), y_scale.range_end()),
+                );
+            }
+        }
+
         Ok(group)
     }
 
@@ -150,11 +268,115 @@ impl<'a, T: Display + Clone, U: Display + Clone> View<'a> for AreaSeriesView<'a,
     fn get_legend_entries(&self) -> Vec<LegendEntry> {
         let mut entries = Vec::new();
 
-        // Area series currently does not support multiple keys per dataset,
-        // hence when displaying a legend, it will display the custom data label
-        // as the legend label.
-        entries.push(LegendEntry::new(LegendMarkerType::Square, self.colors[0].as_hex(), String::from("none"), self.custom_data_label.clone()));
+        // A single series has no keys, hence when displaying a legend, it will
+        // display the custom data label as the legend label.
+        if self.color_map.is_empty() {
+            entries.push(LegendEntry::new(LegendMarkerType::Square, self.colors[0].as_hex(), String::from("none"), self.custom_data_label.clone()));
+        } else {
+            for key in self.keys.iter() {
+                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), key.clone()));
+            }
+        }
 
         entries
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScaleLinear;
+
+    fn area_paths(view: &AreaSeriesView<f32, f32>) -> Vec<String> {
+        view.to_svg().unwrap().to_string()
+            .split(" d=\"")
+            .skip(1)
+            .map(|path| path.split('"').next().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn stacked_layers_share_their_boundaries() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 2_f32])
+            .set_range(vec![0, 200]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 8_f32])
+            .set_range(vec![80, 0]);
+        let data = vec![(0_f32, 2_f32, "a"), (1_f32, 4_f32, "a"), (2_f32, 2_f32, "a"), (0_f32, 2_f32, "b"), (2_f32, 6_f32, "b")];
+        let view = AreaSeriesView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_label_visibility(false)
+            .load_stacked_data(&data)
+            .unwrap();
+
+        // The layer of b has no thickness at x=1, where it has no value.
+        assert_eq!(area_paths(&view), vec![
+            "M0,60 L100,40 L200,60 L200,80 L100,80 L0,80 z",
+            "M0,40 L100,40 L200,0 L200,60 L100,40 L0,60 z",
+        ]);
+        assert_eq!(view.get_legend_entries().len(), 2);
+    }
+
+    #[test]
+    fn stacked_step_layers_share_their_boundaries() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 2_f32])
+            .set_range(vec![0, 200]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 8_f32])
+            .set_range(vec![80, 0]);
+        let data = vec![(0_f32, 2_f32, "a"), (1_f32, 4_f32, "a"), (2_f32, 2_f32, "a"), (0_f32, 2_f32, "b"), (2_f32, 6_f32, "b")];
+        let view = AreaSeriesView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_label_visibility(false)
+            .set_curve(Curve::StepAfter)
+            .load_stacked_data(&data)
+            .unwrap();
+
+        // The bottom of b steps at the same x values as the top of a: at 60 up to x=100, at 40 after it.
+        assert_eq!(area_paths(&view), vec![
+            "M0,60 L100,60 L100,40 L200,40 L200,60 L200,80 L200,80 L100,80 L100,80 L0,80 z",
+            "M0,40 L100,40 L100,40 L200,40 L200,0 L200,60 L200,40 L100,40 L100,60 L0,60 z",
+        ]);
+    }
+
+    #[test]
+    fn silhouette_and_wiggle_offsets() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 2_f32])
+            .set_range(vec![0, 200]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![-8_f32, 8_f32])
+            .set_range(vec![160, 0]);
+        let data = vec![(0_f32, 2_f32, "a"), (1_f32, 4_f32, "a"), (2_f32, 2_f32, "a"), (0_f32, 2_f32, "b"), (2_f32, 6_f32, "b")];
+        let view = AreaSeriesView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_keys(vec![String::from("a"), String::from("b")])
+            .set_stack_offset(StackOffset::Silhouette)
+            .set_label_visibility(false)
+            .load_stacked_data(&data)
+            .unwrap();
+        let paths = area_paths(&view);
+
+        // The bottom of the stack mirrors its top around zero, at y=80.
+        assert_eq!(paths[0], "M0,80 L100,60 L200,100 L200,120 L100,100 L0,100 z");
+        assert_eq!(paths[1], "M0,60 L100,60 L200,40 L200,100 L100,60 L0,80 z");
+
+        // A streamgraph starts on zero and leans into the growth of b.
+        let view = AreaSeriesView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_stack_offset(StackOffset::Wiggle)
+            .set_label_visibility(false)
+            .load_stacked_data(&data)
+            .unwrap();
+        let paths = area_paths(&view);
+
+        assert!(paths[0].ends_with(" L0,80 z"));
+        assert_ne!(paths[0], "M0,80 L100,60 L200,100 L200,120 L100,100 L0,100 z");
+    }
+}
@@ -0,0 +1,220 @@
+use svg::node::Node;
+use svg::node::element::Group;
+use crate::components::box_plot::{BoxPlot, BoxPlotSummary};
+use crate::colors::Color;
+use crate::{Scale, BarDatum};
+use crate::scales::ScaleType;
+use crate::components::DatumRepresentation;
+use crate::views::{view_class, View};
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
+
+/// A View that represents the distribution of the values in each category as vertical box plots.
+pub struct BoxPlotView<'a> {
+    mean_visible: bool,
+    entries: Vec<BoxPlot>,
+    colors: Vec<Color>,
+    x_scale: Option<&'a dyn Scale<String>>,
+    y_scale: Option<&'a dyn Scale<f32>>,
+    custom_data_label: String,
+    class: Option<String>,
+}
+
+impl<'a> BoxPlotView<'a> {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            mean_visible: false,
+            entries: Vec::new(),
+            colors: Color::color_scheme_10(),
+            x_scale: None,
+            y_scale: None,
+            custom_data_label: String::new(),
+            class: None,
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &'a impl Scale<String>) -> Self {
+        self.x_scale = Some(scale);
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
+        self.y_scale = Some(scale);
+        self
+    }
+
+    /// Set the color palette of the view.
+    pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Set the visibility of the dashed line that displays the mean within each box.
+    pub fn set_mean_visibility(mut self, mean_visibility: bool) -> Self {
+        self.mean_visible = mean_visibility;
+        self
+    }
+
+    /// Set custom label for the dataset.
+    pub fn set_custom_data_label(mut self, label: String) -> Self {
+        self.custom_data_label = label;
+        self
+    }
+
+    /// Load and process a dataset of BarDatum points, where all the values
+    /// of a category form the sample summarized by its box plot.
+    pub fn load_data(mut self, data: &[impl BarDatum]) -> Result<Self, ChartError> {
+        let x_scale = match self.x_scale {
+            Some(scale) if scale.get_type() == ScaleType::Band => scale,
+            None => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the X scale should be a band scale"))),
+        };
+        let y_scale = match self.y_scale {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            None => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the Y scale should be a linear scale"))),
+        };
+
+        // Group the samples by category, in the order in which the categories appear.
+        let mut samples: Vec<(String, Vec<f32>)> = Vec::new();
+        for datum in data.iter() {
+            let category = datum.get_category();
+            match samples.iter_mut().find(|(sample_category, _)| *sample_category == category) {
+                Some((_, values)) => values.push(datum.get_value()),
+                None => samples.push((category, vec![datum.get_value()])),
+            }
+        }
+
+        let color = self.colors[0].as_hex();
+        for (category, values) in samples.iter() {
+            if let Some(summary) = BoxPlotSummary::from_samples(values) {
+                let box_plot = BoxPlot::new(
+                    summary.map(|value| y_scale.scale(&value)),
+                    x_scale.scale(category),
+                    x_scale.bandwidth().unwrap(),
+                    color.clone(),
+                ).set_mean_visibility(self.mean_visible);
+                self.entries.push(box_plot);
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+impl<'a> Default for BoxPlotView<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> View<'a> for BoxPlotView<'a> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new().set("class", view_class("box-plot-view", &self.class));
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        vec![LegendEntry::new(LegendMarkerType::Square, self.colors[0].as_hex(), String::from("none"), self.custom_data_label.clone())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScaleBand, ScaleLinear};
+
+    fn line_position(svg: &str, class: &str) -> Option<f32> {
+        svg.lines()
+            .find(|line| line.contains(&format!("class=\"{}\"", class)))
+            .map(|line| line.split("y1=\"").nth(1).unwrap().split('"').next().unwrap().parse::<f32>().unwrap())
+    }
+
+    fn render(data: &[(&str, f32)], y: &ScaleLinear) -> String {
+        let x = ScaleBand::new()
+            .set_domain(vec![String::from("A")])
+            .set_range(vec![0, 200]);
+
+        BoxPlotView::new()
+            .set_x_scale(&x)
+            .set_y_scale(y)
+            .set_mean_visibility(true)
+            .load_data(data)
+            .unwrap()
+            .to_svg()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn mean_and_median_lines_of_skewed_sample() {
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![500, 0]);
+        let data = vec![("A", 1_f32), ("A", 2_f32), ("A", 3_f32), ("A", 4_f32), ("A", 90_f32)];
+        let svg = render(&data, &y);
+
+        assert_eq!(line_position(&svg, "box-median"), Some(y.scale(&3_f32)));
+        assert_eq!(line_position(&svg, "box-mean"), Some(y.scale(&20_f32)));
+        assert!(svg.contains("stroke-dasharray=\"4,2\""));
+    }
+
+    #[test]
+    fn mean_and_median_coincide_for_symmetric_sample() {
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![500, 0]);
+        let data = vec![("A", 10_f32), ("A", 20_f32), ("A", 30_f32), ("A", 40_f32), ("A", 50_f32)];
+        let svg = render(&data, &y);
+
+        assert_eq!(line_position(&svg, "box-median"), line_position(&svg, "box-mean"));
+        assert_eq!(line_position(&svg, "box-median"), Some(y.scale(&30_f32)));
+    }
+
+    #[test]
+    fn outliers_beyond_the_whiskers() {
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 100_f32])
+            .set_range(vec![500, 0]);
+        let mut data = (1..=9).map(|value| ("A", value as f32)).collect::<Vec<(&str, f32)>>();
+        data.push(("A", 100_f32));
+        let svg = render(&data, &y);
+
+        let whisker = svg.lines().find(|line| line.contains("class=\"box-whisker\"")).unwrap();
+        assert!(whisker.contains(&format!("y1=\"{}\"", y.scale(&1_f32))));
+        assert!(whisker.contains(&format!("y2=\"{}\"", y.scale(&9_f32))));
+
+        let outliers = svg.lines().filter(|line| line.contains("class=\"box-outlier\"")).collect::<Vec<&str>>();
+        assert_eq!(outliers.len(), 1);
+        assert!(outliers[0].contains(&format!("cy=\"{}\"", y.scale(&100_f32))));
+    }
+
+    #[test]
+    fn non_finite_samples_are_ignored() {
+        let summary = BoxPlotSummary::from_samples(&[3_f32, f32::NAN, 1_f32, f32::INFINITY, 2_f32, f32::NEG_INFINITY]).unwrap();
+
+        assert_eq!(summary, BoxPlotSummary::from_samples(&[1_f32, 2_f32, 3_f32]).unwrap());
+        assert_eq!(summary.median, 2_f32);
+        assert!(summary.outliers.is_empty());
+
+        assert_eq!(BoxPlotSummary::from_samples(&[f32::NAN, f32::INFINITY]), None);
+        assert_eq!(BoxPlotSummary::from_samples(&[]), None);
+    }
+}
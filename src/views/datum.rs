@@ -12,6 +12,12 @@ pub trait BarDatum {
     /// (just return an empty string), but is required in a stacked bar chart
     /// as the stacked entries are differentiated by the key.
     fn get_key(&self) -> String;
+
+    /// Return the confidence in the value of the datum. This is optional and
+    /// is used to vary the opacity of the bar representing the datum.
+    fn get_confidence(&self) -> Option<f32> {
+        None
+    }
 }
 
 /// A trait that defines interaction with a data point used in a scatter/line plots.
@@ -26,6 +32,12 @@ pub trait PointDatum<T, U> {
     /// only a single type of data (just return an empty string), but is required
     /// in a chart that represents multiple categories of points.
     fn get_key(&self) -> String;
+
+    /// Return the size of the datum. This is optional and is used to scale
+    /// the marker of the point, e.g. in a bubble chart.
+    fn get_size(&self) -> Option<f32> {
+        None
+    }
 }
 
 impl BarDatum for (f32, &str) {
@@ -168,6 +180,42 @@ impl BarDatum for (&str, i32, &str) {
     }
 }
 
+impl BarDatum for (&str, f32, f32) {
+    fn get_category(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+
+    fn get_confidence(&self) -> Option<f32> {
+        Some(self.2)
+    }
+}
+
+impl BarDatum for (&str, f32, &str, f32) {
+    fn get_category(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::from(self.2)
+    }
+
+    fn get_confidence(&self) -> Option<f32> {
+        Some(self.3)
+    }
+}
+
 impl PointDatum<f32, f32> for (f32, f32) {
     fn get_x(&self) -> f32 {
         self.0
@@ -182,6 +230,42 @@ impl PointDatum<f32, f32> for (f32, f32) {
     }
 }
 
+impl PointDatum<f32, f32> for (f32, f32, f32) {
+    fn get_x(&self) -> f32 {
+        self.0
+    }
+
+    fn get_y(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+
+    fn get_size(&self) -> Option<f32> {
+        Some(self.2)
+    }
+}
+
+impl PointDatum<f32, f32> for (f32, f32, f32, &str) {
+    fn get_x(&self) -> f32 {
+        self.0
+    }
+
+    fn get_y(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::from(self.3)
+    }
+
+    fn get_size(&self) -> Option<f32> {
+        Some(self.2)
+    }
+}
+
 impl PointDatum<f32, f32> for (isize, isize) {
     fn get_x(&self) -> f32 {
         self.0 as f32
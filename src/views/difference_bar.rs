@@ -0,0 +1,222 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::AddAssign;
+use svg::node::Node;
+use svg::node::element::{Group, Line};
+use crate::components::bar::{Bar, BarBlock, BarLabelPosition};
+use crate::components::categorised_bars::{CategorisedValues, ToF64};
+use crate::colors::Color;
+use crate::Scale;
+use crate::scales::ScaleType;
+use crate::components::DatumRepresentation;
+use crate::views::{view_class, View};
+use crate::chart::Orientation;
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
+
+/// A View that compares two series by drawing the signed difference (comparison minus baseline)
+/// of each category as a vertical bar from zero, colored by whether the value increased or decreased.
+pub struct DifferenceBarView<'a> {
+    labels_visible: bool,
+    rounding_precision: Option<usize>,
+    entries: Vec<Bar>,
+    baseline: Option<(f32, f32, f32)>,
+    positive_color: Color,
+    negative_color: Color,
+    x_scale: Option<&'a dyn Scale<String>>,
+    y_scale: Option<&'a dyn Scale<f32>>,
+    class: Option<String>,
+}
+
+impl<'a> DifferenceBarView<'a> {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        let colors = Color::color_scheme_10();
+
+        Self {
+            labels_visible: true,
+            rounding_precision: None,
+            entries: Vec::new(),
+            baseline: None,
+            positive_color: colors[2].clone(),
+            negative_color: colors[3].clone(),
+            x_scale: None,
+            y_scale: None,
+            class: None,
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &'a impl Scale<String>) -> Self {
+        self.x_scale = Some(scale);
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
+        self.y_scale = Some(scale);
+        self
+    }
+
+    /// Set the colors of the bars with a positive and a negative difference.
+    pub fn set_colors(mut self, positive: Color, negative: Color) -> Self {
+        self.positive_color = positive;
+        self.negative_color = negative;
+        self
+    }
+
+    /// Set labels visibility.
+    pub fn set_label_visibility(mut self, label_visibility: bool) -> Self {
+        self.labels_visible = label_visibility;
+        self
+    }
+
+    /// Set the precision to which value labels should be rounded.
+    pub fn set_label_rounding_precision(mut self, nr_of_digits: usize) -> Self {
+        self.rounding_precision = Some(nr_of_digits);
+        self
+    }
+
+    /// Load the difference between the category totals of the comparison and the baseline series.
+    /// Categories are taken in the order of the baseline, followed by those only present in the
+    /// comparison, and a category that is missing from one of the series counts as zero there.
+    pub fn load_data<CAT, SEG, VAL>(mut self, baseline: &CategorisedValues<CAT, SEG, VAL>, comparison: &CategorisedValues<CAT, SEG, VAL>) -> Result<Self, ChartError>
+    where
+        CAT: Clone + Default + Display + Hash + Eq,
+        SEG: Clone + Default + Display + Hash + Eq,
+        VAL: AddAssign<VAL> + Copy + Default + Display + ToF64,
+    {
+        let x_scale = match self.x_scale {
+            Some(scale) if scale.get_type() == ScaleType::Band => scale,
+            None => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the X scale should be a band scale"))),
+        };
+        let y_scale = match self.y_scale {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            None => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the Y scale should be a linear scale"))),
+        };
+
+        let total = |values: &CategorisedValues<CAT, SEG, VAL>, key: &CAT| {
+            values.category(key).map_or(0_f64, |category| category.height().to_f64())
+        };
+        let mut keys = baseline.category_keys().collect::<Vec<&CAT>>();
+        for key in comparison.category_keys() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let zero = y_scale.scale(&0_f32);
+        let y_range_is_reversed = y_scale.is_range_reversed();
+        self.baseline = Some((x_scale.range_start(), x_scale.range_end(), zero));
+
+        for key in keys {
+            let difference = (total(comparison, key) - total(baseline, key)) as f32;
+            let position = y_scale.scale(&difference);
+            let color = if difference < 0_f32 { &self.negative_color } else { &self.positive_color };
+            // Place the label beyond the end of the bar that points away from the baseline.
+            let label_position = if (difference >= 0_f32) == y_range_is_reversed {
+                BarLabelPosition::EndOutside
+            } else {
+                BarLabelPosition::StartOutside
+            };
+            let category = key.to_string();
+
+            let block = BarBlock::new(f32::min(zero, position), f32::max(zero, position), difference, color.as_hex(), String::from("difference"));
+            let bar = Bar::new(vec![block], Orientation::Vertical, category.clone(), label_position, self.labels_visible, self.rounding_precision, x_scale.bandwidth().unwrap(), x_scale.scale(&category));
+            self.entries.push(bar);
+        }
+
+        Ok(self)
+    }
+}
+
+impl<'a> Default for DifferenceBarView<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> View<'a> for DifferenceBarView<'a> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new().set("class", view_class("difference-bar-view", &self.class));
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        if let Some((start, end, position)) = self.baseline {
+            group.append(
+                Line::new()
+                    .set("class", "difference-baseline")
+                    .set("x1", start)
+                    .set("y1", position)
+                    .set("x2", end)
+                    .set("y2", position)
+                    .set("stroke", "#333")
+                    .set("stroke-width", 1)
+            );
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        vec![
+            LegendEntry::new(LegendMarkerType::Square, self.positive_color.as_hex(), String::from("none"), String::from("Increase")),
+            LegendEntry::new(LegendMarkerType::Square, self.negative_color.as_hex(), String::from("none"), String::from("Decrease")),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::test_support::*;
+
+    #[test]
+    fn difference_bars_by_sign() {
+        let x = band_scale(&["A", "B", "C"], vec![0, 600]);
+        let y = linear_scale(-50_f32, 50_f32, vec![400, 0]);
+        let year_2019 = CategorisedValues::new().add_data(vec![("A", 30), ("B", 40), ("C", 10)]);
+        let year_2020 = CategorisedValues::new().add_data(vec![("A", 50), ("B", 15), ("C", 10)]);
+
+        let view = DifferenceBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&year_2019, &year_2020)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let bars = svg.lines().filter(|line| line.starts_with("<rect")).collect::<Vec<&str>>();
+        let zero = y.scale(&0_f32);
+
+        assert_eq!(bars.len(), 3);
+
+        // A increased by 20, so its bar goes up from the baseline.
+        assert!(bars[0].contains("fill=\"#2ca02c\""));
+        assert_eq!(attribute(bars[0], "y"), y.scale(&20_f32));
+        assert_eq!(attribute(bars[0], "y") + attribute(bars[0], "height"), zero);
+
+        // B decreased by 25, so its bar goes down from the baseline.
+        assert!(bars[1].contains("fill=\"#d62728\""));
+        assert_eq!(attribute(bars[1], "y"), zero);
+        assert_eq!(attribute(bars[1], "y") + attribute(bars[1], "height"), y.scale(&-25_f32));
+
+        // C did not change.
+        assert_eq!(attribute(bars[2], "height"), 0_f32);
+
+        assert!(svg.contains(">\n20\n</text>"));
+        assert!(svg.contains(">\n-25\n</text>"));
+        assert!(svg.contains(&format!("class=\"difference-baseline\" stroke=\"#333\" stroke-width=\"1\" x1=\"0\" x2=\"600\" y1=\"{}\"", zero)));
+    }
+}
@@ -1,27 +1,40 @@
 use std::collections::HashMap;
 use svg::node::Node;
 use svg::node::element::Group;
-use crate::components::bar::{Bar, BarBlock, BarLabelPosition};
+use crate::components::bar::{segment_connectors, Bar, BarBlock, BarLabelPosition, BarLayout};
 use crate::colors::Color;
 use crate::{Scale, BarDatum};
 use crate::scales::ScaleType;
 use crate::components::DatumRepresentation;
-use crate::views::View;
+use crate::views::{apply_drop_shadow, grouped_bar_slots, rendered_bar_width, CategoryEntries, view_class, View};
 use crate::chart::Orientation;
-use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::components::legend::{LegendEntry, LegendMarkerType, LegendValueMode};
+use crate::ChartError;
+use crate::components::IdScope;
 
 /// A View that represents data as horizontal bars.
 pub struct HorizontalBarView<'a> {
     label_position: BarLabelPosition,
+    layout: BarLayout,
     labels_visible: bool,
+    total_labels_visible: bool,
+    segment_connectors_visible: bool,
+    normalized: bool,
+    shadow: bool,
+    legend_values: LegendValueMode,
+    key_totals: HashMap<String, f32>,
     rounding_precision: Option<usize>,
+    corner_radius: Option<f32>,
+    bar_width: Option<usize>,
     entries: Vec<Bar>,
     keys: Vec<String>,
     colors: Vec<Color>,
     color_map: HashMap<String, String>,
     x_scale: Option<&'a dyn Scale<f32>>,
     y_scale: Option<&'a dyn Scale<String>>,
+    opacity_scale: Option<&'a dyn Scale<f32>>,
     custom_data_label: String,
+    class: Option<String>,
 }
 
 impl<'a> HorizontalBarView<'a> {
@@ -29,15 +42,26 @@ impl<'a> HorizontalBarView<'a> {
     pub fn new() -> Self {
         Self {
             label_position: BarLabelPosition::EndOutside,
+            layout: BarLayout::Stacked,
             labels_visible: true,
+            total_labels_visible: false,
+            segment_connectors_visible: false,
+            normalized: false,
+            shadow: false,
+            legend_values: LegendValueMode::None,
+            key_totals: HashMap::new(),
             rounding_precision: None,
+            corner_radius: None,
+            bar_width: None,
             entries: Vec::new(),
             keys: Vec::new(),
             colors: Color::color_scheme_10(),
             color_map: HashMap::new(),
             x_scale: None,
             y_scale: None,
+            opacity_scale: None,
             custom_data_label: String::new(),
+            class: None,
         }
     }
 
@@ -53,6 +77,14 @@ impl<'a> HorizontalBarView<'a> {
         self
     }
 
+    /// Set the scale that maps the confidence of each datum to the fill opacity of its bar.
+    /// The scale output is clamped between 0 and 1, e.g. a linear scale with a range of
+    /// `vec![0, 1]` maps confidences 0 to 1 directly onto opacities.
+    pub fn set_opacity_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
+        self.opacity_scale = Some(scale);
+        self
+    }
+
     /// Set the keys in case of a stacked bar chart.
     pub fn set_keys(mut self, keys: Vec<String>) -> Self {
         self.keys = keys;
@@ -65,6 +97,12 @@ impl<'a> HorizontalBarView<'a> {
         self
     }
 
+    /// Set whether the keys of a category are stacked in a single bar, or placed side by side.
+    pub fn set_layout(mut self, layout: BarLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
     /// Set the color palette of the view.
     pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
         self.colors = colors;
@@ -77,6 +115,40 @@ impl<'a> HorizontalBarView<'a> {
         self
     }
 
+    /// Set the visibility of the labels that display the total value of each stacked bar.
+    /// Grouped bars have no total, so loading data fails when both are set.
+    pub fn set_total_label_visibility(mut self, total_label_visibility: bool) -> Self {
+        self.total_labels_visible = total_label_visibility;
+        self
+    }
+
+    /// Set the visibility of the bands that connect the blocks of the same key
+    /// in adjacent bars of a stacked bar chart.
+    pub fn set_segment_connectors_visibility(mut self, connectors_visibility: bool) -> Self {
+        self.segment_connectors_visible = connectors_visibility;
+        self
+    }
+
+    /// Set whether each stacked bar should be scaled to the full width, with its blocks sized
+    /// and labeled by their percentage of the category total. The X scale should then span 0 to 100.
+    pub fn set_normalized(mut self, normalized: bool) -> Self {
+        self.normalized = normalized;
+        self
+    }
+
+    /// Set whether the bars should cast a drop shadow.
+    pub fn set_shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    /// Set the value that is appended to the label of each legend entry: the total of the key,
+    /// or its share of the grand total.
+    pub fn set_legend_values(mut self, mode: LegendValueMode) -> Self {
+        self.legend_values = mode;
+        self
+    }
+
     /// Set custom label for the dataset.
     /// This will work when the dataset represents only a single
     /// type of data (i.e. there are no different "keys" by which to
@@ -92,15 +164,27 @@ impl<'a> HorizontalBarView<'a> {
         self
     }
 
+    /// Set the radius of the rounded corners at the end of the bars.
+    pub fn set_corner_radius(mut self, corner_radius: f32) -> Self {
+        self.corner_radius = Some(corner_radius);
+        self
+    }
+
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl BarDatum>) -> Result<Self, String> {
+    pub fn load_data(mut self, data: &Vec<impl BarDatum>) -> Result<Self, ChartError> {
         match self.x_scale {
             Some(scale) if scale.get_type() == ScaleType::Linear => {},
-            _ => return Err("The X axis scale should be a Band scale.".to_string()),
+            None => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the X scale should be a linear scale"))),
         }
         match self.y_scale {
             Some(scale) if scale.get_type() == ScaleType::Band => {},
-            _ => return Err("The Y axis scale should be a Linear scale.".to_string()),
+            None => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the Y scale should be a band scale"))),
+        }
+
+        if self.layout == BarLayout::Grouped && self.total_labels_visible {
+            return Err(ChartError::InvalidOption(String::from("total labels can only be shown for stacked bars")));
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -108,9 +192,14 @@ impl<'a> HorizontalBarView<'a> {
             self.keys = Self::extract_keys(&data);
         }
 
+        // The position of each key within the band of a category, when the keys are grouped.
+        let key_indices = self.keys.iter().cloned().enumerate().map(|(i, key)| (key, i)).collect::<HashMap<String, usize>>();
+        let key_slots = grouped_bar_slots(&self.keys, self.y_scale.unwrap().bandwidth().unwrap());
+        self.bar_width = rendered_bar_width(self.layout, self.y_scale.unwrap().bandwidth().unwrap(), &key_slots);
+
         // HashMap to group all data related to a category. This is needed when there
         // are many data entries under a single category as in a stacked bar chart.
-        let mut categories: HashMap<String, Vec<(&String, f32)>> = HashMap::new();
+        let mut categories: CategoryEntries = HashMap::new();
 
         // Organize entries based on the order of the keys first, since displayed data
         // should keep the order defined in the `keys` attribute.
@@ -121,12 +210,13 @@ impl<'a> HorizontalBarView<'a> {
             for entry in data.iter() {
                 if entry.get_key() == *key {
                     let entry_category = entry.get_category();
+                    *self.key_totals.entry(key.clone()).or_insert(0_f32) += entry.get_value();
 
                     if !categories.contains_key(&entry_category) {
                         categories.insert(entry.get_category(), Vec::new());
                     }
                     if let Some(category_entries) = categories.get_mut(&entry_category) {
-                        category_entries.push((key, entry.get_value()));
+                        category_entries.push((key, entry.get_value(), entry.get_confidence()));
                     }
                 }
             }
@@ -137,12 +227,45 @@ impl<'a> HorizontalBarView<'a> {
         let x_range_is_reversed = self.x_scale.unwrap().is_range_reversed();
 
         for (category, key_value_pairs) in categories.iter_mut() {
+            if self.normalized {
+                let total = key_value_pairs.iter().map(|(_, value, _)| *value).sum::<f32>();
+                if total != 0_f32 {
+                    for (_, value, _) in key_value_pairs.iter_mut() {
+                        *value = *value / total * 100_f32;
+                    }
+                }
+            }
+
+            if self.layout == BarLayout::Grouped {
+                let x_scale = self.x_scale.unwrap();
+                let y_scale = self.y_scale.unwrap();
+                let zero = x_scale.scale(&0_f32);
+
+                for (key, value, confidence) in key_value_pairs.iter() {
+                    let position = x_scale.scale(value);
+                    let opacity = match (self.opacity_scale, confidence) {
+                        (Some(scale), Some(confidence)) => Some(scale.scale(confidence).clamp(0_f32, 1_f32)),
+                        _ => None,
+                    };
+                    let block = BarBlock::new(f32::min(zero, position), f32::max(zero, position), *value, self.color_map.get(*key).unwrap().clone(), key.to_string())
+                        .set_opacity(opacity);
+                    let (slot_offset, bar_width) = key_slots[key_indices[*key]];
+                    let offset = y_scale.scale(category) + slot_offset;
+
+                    bars.push(
+                        Bar::new(vec![block], Orientation::Horizontal, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, bar_width, offset)
+                            .set_corner_radius(self.corner_radius)
+                    );
+                }
+                continue;
+            }
+
             let mut value_acc = 0_f32;
             let mut bar_blocks = Vec::new();
             let mut stacked_start = self.x_scale.unwrap().scale(&value_acc);
             let mut stacked_end = stacked_start;
 
-            for (key, value) in key_value_pairs.iter() {
+            for (key, value, confidence) in key_value_pairs.iter() {
                 value_acc += *value;
 
                 if x_range_is_reversed {
@@ -152,10 +275,19 @@ impl<'a> HorizontalBarView<'a> {
                     stacked_start = stacked_end;
                     stacked_end = self.x_scale.unwrap().scale(&value_acc);
                 }
-                bar_blocks.push(BarBlock::new(stacked_start, stacked_end, *value, self.color_map.get(*key).unwrap().clone()));
+                let opacity = match (self.opacity_scale, confidence) {
+                    (Some(scale), Some(confidence)) => Some(scale.scale(confidence).clamp(0_f32, 1_f32)),
+                    _ => None,
+                };
+                bar_blocks.push(
+                    BarBlock::new(stacked_start, stacked_end, *value, self.color_map.get(*key).unwrap().clone(), key.to_string())
+                        .set_opacity(opacity)
+                );
             }
 
-            let bar = Bar::new(bar_blocks, Orientation::Horizontal, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, self.y_scale.unwrap().bandwidth().unwrap(), self.y_scale.unwrap().scale(category));
+            let bar = Bar::new(bar_blocks, Orientation::Horizontal, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, self.y_scale.unwrap().bandwidth().unwrap(), self.y_scale.unwrap().scale(category))
+                .set_total_label_visibility(self.total_labels_visible)
+                .set_corner_radius(self.corner_radius);
             bars.push(bar);
         }
 
@@ -166,6 +298,12 @@ impl<'a> HorizontalBarView<'a> {
         Ok(self)
     }
 
+    /// The width of the bars as laid out by `load_data`, so that overlays can match it.
+    /// Grouped bars report the narrowest bar. `None` until the data is loaded.
+    pub fn rendered_bar_width(&self) -> Option<usize> {
+        self.bar_width
+    }
+
     /// Extract the list of keys to use when stacking and coloring the bars.
     fn extract_keys(data: &Vec<impl BarDatum>) -> Vec<String> {
         let mut keys = Vec::new();
@@ -188,33 +326,132 @@ impl<'a> HorizontalBarView<'a> {
 }
 
 impl<'a> View<'a> for HorizontalBarView<'a> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new();
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let _ids = IdScope::enter();
+        let mut group = Group::new().set("class", view_class("horizontal-bar-view", &self.class));
+
+        if self.segment_connectors_visible {
+            group.append(segment_connectors(&self.entries));
+        }
 
         for entry in self.entries.iter() {
             let child_svg = entry.to_svg()?;
             group.append(child_svg);
         }
 
+        if self.shadow {
+            group = apply_drop_shadow(group);
+        }
+
         Ok(group)
     }
 
     /// Return the legend entries that this view represents.
     fn get_legend_entries(&self) -> Vec<LegendEntry> {
         let mut entries = Vec::new();
+        let grand_total = self.key_totals.values().sum::<f32>();
+        let label = |key: &String, label: &str| {
+            self.legend_values.append_to(label, self.key_totals.get(key).cloned().unwrap_or(0_f32), grand_total)
+        };
 
         // If there is a single key and it is an empty string (meaning
         // the dataset consists only of X and Y dimension values), return
         // the custom data label.
         if self.keys.len() == 1 && self.keys[0].len() == 0 {
-            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), self.custom_data_label.clone()));
+            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), label(&self.keys[0], &self.custom_data_label)));
         } else {
             for key in self.keys.iter() {
-                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), key.clone()));
+                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), label(key, key)));
             }
         }
 
         entries
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::test_support::*;
+
+    #[test]
+    fn grouped_keys_share_the_band() {
+        let x = linear_scale(0_f32, 200_f32, vec![0, 600]);
+        let y = band_scale(&["1977", "1978"], vec![0, 400]);
+        let data = vec![
+            ("1977", 100_f32, "Cassette"),
+            ("1977", 150_f32, "8 - Track"),
+            ("1977", 50_f32, "LP/EP"),
+        ];
+
+        let view = HorizontalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_label_visibility(false)
+            .set_layout(BarLayout::Grouped)
+            .load_data(&data)
+            .unwrap();
+        let bars = bar_blocks(&view.to_svg().unwrap().to_string());
+        assert_eq!(bars.len(), 3);
+
+        // The bars follow each other without overlap and together fill the band of the category.
+        let band_start = y.scale(&String::from("1977"));
+        assert_eq!(bars[0].0, (0_f32, band_start));
+        for pair in bars.windows(2) {
+            assert!((pair[0].0.1 + pair[0].2 - pair[1].0.1).abs() < 1e-3);
+        }
+        let total_height = bars.iter().map(|bar| bar.2).sum::<f32>();
+        assert!((total_height - y.bandwidth().unwrap()).abs() < 1e-3);
+        assert_eq!(bars.iter().map(|bar| bar.1).collect::<Vec<f32>>(), vec![300_f32, 450_f32, 150_f32]);
+    }
+
+    #[test]
+    fn rendered_bar_width_follows_the_band() {
+        let x = linear_scale(0_f32, 200_f32, vec![0, 600]);
+        let y = band_scale(&["1977", "1978", "1979", "1980"], vec![0, 400])
+            .set_inner_padding(0_f32)
+            .set_outer_padding(0_f32);
+        let data = vec![("1977", 100_f32, "Cassette"), ("1978", 150_f32, "Cassette")];
+
+        let view = HorizontalBarView::new().set_x_scale(&x).set_y_scale(&y).load_data(&data).unwrap();
+        assert_eq!(view.rendered_bar_width(), Some(100));
+    }
+
+    #[test]
+    fn normalized_bars_fill_the_plot_width() {
+        let x = linear_scale(0_f32, 100_f32, vec![0, 600]);
+        let y = band_scale(&["1977", "1978", "1979"], vec![0, 400]);
+        let data = vec![
+            ("1977", 36.9, "Cassette"),
+            ("1977", 127.3, "8 - Track"),
+            ("1978", 133.6, "8 - Track"),
+            ("1978", 61.3, "Cassette"),
+            ("1979", 102.3, "8 - Track"),
+        ];
+
+        let view = HorizontalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_normalized(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let widths = svg
+            .lines()
+            .filter(|line| line.starts_with("<rect"))
+            .map(|line| attribute(line, "width"))
+            .collect::<Vec<f32>>();
+
+        assert_eq!(widths.len(), 5);
+        assert!((widths.iter().sum::<f32>() - 3_f32 * 600_f32).abs() < 1e-2);
+        assert!(widths.contains(&600_f32));
+        assert!(svg.contains(">\n100\n</text>"));
+    }
+}
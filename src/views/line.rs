@@ -2,19 +2,24 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use svg::node::Node;
 use svg::node::element::Group;
+use crate::components::area::Curve;
 use crate::components::scatter::{ScatterPoint, MarkerType, PointLabelPosition};
 use crate::colors::Color;
 use crate::{Scale, LineSeries};
 use crate::views::datum::PointDatum;
-use crate::views::View;
+use crate::views::{clip_to_plot_area, view_class, View};
 use crate::components::DatumRepresentation;
 use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
+use crate::components::IdScope;
 
 /// A View that represents data as a scatter plot.
 pub struct LineSeriesView<'a, T: Display, U: Display> {
     labels_visible: bool,
+    clip_to_plot_area: bool,
     label_position: PointLabelPosition,
     marker_type: MarkerType,
+    curve: Curve,
     entries: Vec<LineSeries<T, U>>,
     colors: Vec<Color>,
     keys: Vec<String>,
@@ -22,6 +27,7 @@ pub struct LineSeriesView<'a, T: Display, U: Display> {
     x_scale: Option<&'a dyn Scale<T>>,
     y_scale: Option<&'a dyn Scale<U>>,
     custom_data_label: String,
+    class: Option<String>,
 }
 
 impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
@@ -29,8 +35,10 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
     pub fn new() -> Self {
         Self {
             labels_visible: true,
+            clip_to_plot_area: false,
             label_position: PointLabelPosition::NW,
             marker_type: MarkerType::Circle,
+            curve: Curve::Linear,
             entries: Vec::new(),
             keys: Vec::new(),
             colors: Color::color_scheme_10(),
@@ -38,6 +46,7 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
             x_scale: None,
             y_scale: None,
             custom_data_label: String::new(),
+            class: None,
         }
     }
 
@@ -83,6 +92,19 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
         self
     }
 
+    /// Set the way in which the lines connect the points, e.g. in a smooth curve.
+    pub fn set_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Set whether the view should be clipped to the plot area, so that
+    /// points which fall outside of the scales' domains are not rendered.
+    pub fn set_clip(mut self, clip: bool) -> Self {
+        self.clip_to_plot_area = clip;
+        self
+    }
+
     /// Set custom label for the dataset.
     /// This will work when the dataset represents only a single
     /// type of data (i.e. there are no different "keys" by which to
@@ -93,14 +115,14 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
     }
 
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, String> {
+    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, ChartError> {
         match self.x_scale {
             Some(_) => {},
-            _ => return Err("Please provide a scale for the X dimension before loading data".to_string()),
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
         }
         match self.y_scale {
             Some(_) => {},
-            _ => return Err("Please provide a scale for the Y dimension before loading data".to_string()),
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -120,24 +142,17 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
             let points = data.iter().filter(|datum| &datum.get_key() == key).map(|datum| {
                 let scaled_x = self.x_scale.unwrap().scale(&datum.get_x());
                 let scaled_y = self.y_scale.unwrap().scale(&datum.get_y());
-                let y_bandwidth_offset = {
-                    if self.y_scale.unwrap().is_range_reversed() {
-                        -self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
-                    } else {
-                        self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
-                    }
-                };
-                let x_bandwidth_offset = {
-                    if self.x_scale.unwrap().is_range_reversed() {
-                        -self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
-                    } else {
-                        self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
-                    }
-                };
+                let y_bandwidth_offset = self.y_scale.unwrap().bandwidth().unwrap() / 2_f32;
+                let x_bandwidth_offset = self.x_scale.unwrap().bandwidth().unwrap() / 2_f32;
                 ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, 5, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true,self.color_map.get(&datum.get_key()).unwrap().clone())
             }).collect::<Vec<ScatterPoint<T, U>>>();
 
-            self.entries.push(LineSeries::new(points, self.color_map.get(key).unwrap().clone()));
+            // A key without data has no line to draw, but keeps its entry in the legend.
+            if points.is_empty() {
+                continue;
+            }
+
+            self.entries.push(LineSeries::new(points, self.color_map.get(key).unwrap().clone()).set_curve(self.curve));
         }
 
         Ok(self)
@@ -161,15 +176,32 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
 }
 
 impl<'a, T: Display, U: Display> View<'a> for LineSeriesView<'a, T, U> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new();
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let _ids = IdScope::enter();
+        let mut group = Group::new().set("class", view_class("line-view", &self.class));
 
         for entry in self.entries.iter() {
             let child_svg = entry.to_svg()?;
             group.append(child_svg);
         }
 
+        if self.clip_to_plot_area {
+            if let (Some(x_scale), Some(y_scale)) = (self.x_scale, self.y_scale) {
+                group = clip_to_plot_area(
+                    group,
+                    (x_scale.range_start(), x_scale.range_end()),
+                    (y_scale.range_start(), y_scale.range_end()),
+                );
+            }
+        }
+
         Ok(group)
     }
 
@@ -191,3 +223,31 @@ impl<'a, T: Display, U: Display> View<'a> for LineSeriesView<'a, T, U> {
         entries
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScaleLinear;
+
+    #[test]
+    fn keys_without_data_are_skipped() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let data = vec![(1_f32, 2_f32, "a"), (5_f32, 7_f32, "a")];
+
+        let view = LineSeriesView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_keys(vec![String::from("a"), String::from("b")])
+            .load_data(&data)
+            .unwrap();
+
+        let svg = view.to_svg().unwrap().to_string();
+        assert_eq!(svg.matches("class=\"line\"").count(), 1);
+        assert_eq!(view.get_legend_entries().len(), 2);
+    }
+}
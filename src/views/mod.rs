@@ -1,5 +1,11 @@
-use svg::node::element::Group;
+use std::collections::HashMap;
+use svg::node::element::{ClipPath, Definitions, Element, Filter, Group, Rectangle};
+use svg::Node;
+use crate::components::bar::BarLayout;
+use crate::components::categorised_bars::{BarGroup, BarLabel};
 use crate::components::legend::LegendEntry;
+use crate::components::unique_id;
+use crate::ChartError;
 
 pub mod vertical_bar;
 pub mod horizontal_bar;
@@ -7,10 +13,148 @@ pub mod scatter;
 pub mod datum;
 pub mod line;
 pub mod area;
+pub mod box_plot;
+pub mod difference_bar;
+pub mod annotation;
+
+/// The (key, value, confidence) entries of a bar chart, grouped by category.
+pub(crate) type CategoryEntries<'k> = HashMap<String, Vec<(&'k String, f32, Option<f32>)>>;
+
+/// The offset and width within a band of the bar of each key, in the order of the keys, when the
+/// keys of a category are placed side by side. The bars are laid out in whole pixels by a [BarGroup]
+/// and then stretched to fill the bandwidth exactly.
+pub(crate) fn grouped_bar_slots(keys: &[String], bandwidth: f32) -> Vec<(f32, f32)> {
+    if keys.is_empty() {
+        return Vec::new();
+    }
+
+    let group = BarGroup::new("keys").define_labels(keys.iter().enumerate().map(|(index, key)| BarLabel::from((index, key))));
+    let dimension = usize::max(bandwidth.round() as usize, keys.len());
+    let ratio = bandwidth / dimension as f32;
+
+    let mut slots = vec![(0_f32, 0_f32); keys.len()];
+    for bar in group.bar_positions(dimension) {
+        // The layout of a group starts at position 1.
+        slots[bar.key] = ((bar.position_start - 1) as f32 * ratio, bar.width() as f32 * ratio);
+    }
+
+    slots
+}
+
+/// The width in whole pixels of the bars laid out in a band: the bandwidth when the keys are
+/// stacked, or the narrowest of the `grouped_bar_slots` when they are grouped.
+pub(crate) fn rendered_bar_width(layout: BarLayout, bandwidth: f32, key_slots: &[(f32, f32)]) -> Option<usize> {
+    match layout {
+        BarLayout::Stacked => Some(bandwidth.floor() as usize),
+        BarLayout::Grouped => key_slots.iter().map(|(_, width)| width.floor() as usize).min(),
+    }
+}
 
 /// A trait that defines a View of a dataset that can be rendered within a chart.
 pub trait View<'a> {
-    fn to_svg(&self) -> Result<Group, String>;
+    fn to_svg(&self) -> Result<Group, ChartError>;
 
     fn get_legend_entries(&self) -> Vec<LegendEntry>;
+
+    /// Set a class to add to the group of the view, next to the class of the kind of view.
+    fn set_class(self, class: &str) -> Self
+    where
+        Self: Sized;
+}
+
+/// The class of the group of a view: the class of the kind of view, followed by the class set by the user (if any).
+pub(crate) fn view_class(view_class: &str, custom_class: &Option<String>) -> String {
+    match custom_class {
+        Some(custom_class) => format!("{} {}", view_class, custom_class),
+        None => view_class.to_owned(),
+    }
+}
+
+/// Wrap the group of a view in a group that clips its content to the plot area
+/// spanned by the given ranges of the X and Y scales.
+pub(crate) fn clip_to_plot_area(group: Group, x_range: (f32, f32), y_range: (f32, f32)) -> Group {
+    let clip_id = unique_id("clip");
+    let clip_path = ClipPath::new()
+        .set("id", clip_id.as_str())
+        .add(
+            Rectangle::new()
+                .set("x", f32::min(x_range.0, x_range.1))
+                .set("y", f32::min(y_range.0, y_range.1))
+                .set("width", (x_range.1 - x_range.0).abs())
+                .set("height", (y_range.1 - y_range.0).abs())
+        );
+
+    let mut clipped_group = Group::new();
+    clipped_group.append(Definitions::new().add(clip_path));
+    clipped_group.append(group.set("clip-path", format!("url(#{})", clip_id)));
+
+    clipped_group
+}
+
+/// Wrap the group of a view in a group that defines a drop shadow filter and applies it to the view.
+pub(crate) fn apply_drop_shadow(group: Group) -> Group {
+    let shadow_id = unique_id("shadow");
+    let mut drop_shadow = Element::new("feDropShadow");
+    drop_shadow.assign("dx", 2);
+    drop_shadow.assign("dy", 2);
+    drop_shadow.assign("stdDeviation", 2);
+    drop_shadow.assign("flood-opacity", 0.3);
+    let filter = Filter::new()
+        .set("id", shadow_id.as_str())
+        .set("x", "-20%")
+        .set("y", "-20%")
+        .set("width", "140%")
+        .set("height", "140%")
+        .add(drop_shadow);
+
+    let mut shadowed_group = Group::new();
+    shadowed_group.append(Definitions::new().add(filter));
+    shadowed_group.append(group.set("filter", format!("url(#{})", shadow_id)));
+
+    shadowed_group
+}
+
+/// Scales and readers of the rendered SVG shared by the tests of the views and the chart.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use crate::{ScaleBand, ScaleLinear};
+
+    /// A band scale of the categories over the range.
+    pub(crate) fn band_scale(categories: &[&str], range: Vec<isize>) -> ScaleBand {
+        ScaleBand::new()
+            .set_domain(categories.iter().map(|category| category.to_string()).collect())
+            .set_range(range)
+    }
+
+    /// A linear scale of the domain from `start` to `end` over the range.
+    pub(crate) fn linear_scale(start: f32, end: f32, range: Vec<isize>) -> ScaleLinear {
+        ScaleLinear::new()
+            .set_domain(vec![start, end])
+            .set_range(range)
+    }
+
+    /// The value of the first numeric attribute with the given name in the SVG.
+    pub(crate) fn attribute(svg: &str, name: &str) -> f32 {
+        svg.split(&format!(" {}=\"", name)).nth(1).unwrap().split('"').next().unwrap().parse::<f32>().unwrap()
+    }
+
+    /// The offset of the first translation in the SVG.
+    pub(crate) fn translation(svg: &str) -> (f32, f32) {
+        let offset = svg.split("translate(").nth(1).unwrap().split(')').next().unwrap();
+        let (x, y) = offset.split_once(',').unwrap();
+
+        (x.parse::<f32>().unwrap(), y.parse::<f32>().unwrap())
+    }
+
+    /// The offset, width and height of the first block of each bar in the SVG, ordered by their offsets.
+    pub(crate) fn bar_blocks(svg: &str) -> Vec<((f32, f32), f32, f32)> {
+        let mut bars = svg
+            .split("class=\"bar\"")
+            .skip(1)
+            .map(|bar| (translation(bar), attribute(bar, "width"), attribute(bar, "height")))
+            .collect::<Vec<((f32, f32), f32, f32)>>();
+        bars.sort_by(|a, b| a.0.0.total_cmp(&b.0.0).then(a.0.1.total_cmp(&b.0.1)));
+
+        bars
+    }
 }
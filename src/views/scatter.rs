@@ -1,27 +1,41 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 use svg::node::Node;
-use svg::node::element::Group;
+use svg::node::element::{Group, Line};
 use crate::components::scatter::{ScatterPoint, MarkerType, PointLabelPosition};
 use crate::colors::Color;
 use crate::Scale;
+use crate::stats::{linear_regression, LinearFit};
 use crate::views::datum::PointDatum;
-use crate::views::View;
-use crate::components::DatumRepresentation;
+use crate::views::{clip_to_plot_area, view_class, View};
+use crate::components::{AverageCharWidthMeasurer, DatumRepresentation};
 use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
+use crate::components::IdScope;
+
+/// The radius of the markers, when it is not given by the size of the datum.
+const DEFAULT_MARKER_SIZE: usize = 5;
 
 /// A View that represents data as a scatter plot.
 pub struct ScatterView<'a, T: Display, U: Display> {
     labels_visible: bool,
+    tooltips_visible: bool,
+    clip_to_plot_area: bool,
+    trend_line_visible: bool,
+    dense_labels_hidden: bool,
+    point_labels: Vec<String>,
     label_position: PointLabelPosition,
     marker_type: MarkerType,
+    default_marker_size: usize,
     entries: Vec<ScatterPoint<T, U>>,
     colors: Vec<Color>,
     keys: Vec<String>,
     color_map: HashMap<String, String>,
     x_scale: Option<&'a dyn Scale<T>>,
     y_scale: Option<&'a dyn Scale<U>>,
+    size_scale: Option<&'a dyn Scale<f32>>,
     custom_data_label: String,
+    class: Option<String>,
 }
 
 impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
@@ -29,15 +43,23 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
     pub fn new() -> Self {
         Self {
             labels_visible: true,
+            tooltips_visible: false,
+            clip_to_plot_area: false,
+            trend_line_visible: false,
+            dense_labels_hidden: false,
+            point_labels: Vec::new(),
             label_position: PointLabelPosition::NW,
             marker_type: MarkerType::Circle,
+            default_marker_size: DEFAULT_MARKER_SIZE,
             entries: Vec::new(),
             keys: Vec::new(),
             colors: Color::color_scheme_10(),
             color_map: HashMap::new(),
             x_scale: None,
             y_scale: None,
+            size_scale: None,
             custom_data_label: String::new(),
+            class: None,
         }
     }
 
@@ -65,6 +87,19 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
         self
     }
 
+    /// Set the scale that converts the sizes of the data into the radii of the markers,
+    /// e.g. a square root scale so that the area of a bubble is proportional to its size.
+    pub fn set_size_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
+        self.size_scale = Some(scale);
+        self
+    }
+
+    /// Set the radius of the markers of the data without a size, or with a size of zero.
+    pub fn set_default_marker_size(mut self, marker_size: usize) -> Self {
+        self.default_marker_size = marker_size;
+        self
+    }
+
     /// Set the keys in case of a stacked bar chart.
     pub fn set_marker_type(mut self, marker_type: MarkerType) -> Self {
         self.marker_type = marker_type;
@@ -83,6 +118,38 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
         self
     }
 
+    /// Set the visibility of the tooltips that list the coordinates of each point.
+    pub fn set_tooltip_visibility(mut self, tooltip_visibility: bool) -> Self {
+        self.tooltips_visible = tooltip_visibility;
+        self
+    }
+
+    /// Set whether the view should be clipped to the plot area, so that
+    /// points which fall outside of the scales' domains are not rendered.
+    pub fn set_clip(mut self, clip: bool) -> Self {
+        self.clip_to_plot_area = clip;
+        self
+    }
+
+    /// Set the texts to display beside the points instead of their coordinates, e.g. the names
+    /// of the countries that the points represent. The labels are matched to the data in order.
+    pub fn set_point_labels(mut self, labels: Vec<String>) -> Self {
+        self.point_labels = labels;
+        self
+    }
+
+    /// Set whether labels that would overlap a label of a previous point should be hidden.
+    pub fn set_dense_label_hiding(mut self, dense_label_hiding: bool) -> Self {
+        self.dense_labels_hidden = dense_label_hiding;
+        self
+    }
+
+    /// Set the visibility of the least-squares trend line through all the points.
+    pub fn set_trend_line_visibility(mut self, trend_line_visibility: bool) -> Self {
+        self.trend_line_visible = trend_line_visibility;
+        self
+    }
+
     /// Set custom label for the dataset.
     /// This will work when the dataset represents only a single
     /// type of data (i.e. there are no different "keys" by which to
@@ -93,14 +160,14 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
     }
 
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, String> {
+    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, ChartError> {
         match self.x_scale {
             Some(_) => {},
-            _ => return Err("Please provide a scale for the X dimension before loading data".to_string()),
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
         }
         match self.y_scale {
             Some(_) => {},
-            _ => return Err("Please provide a scale for the Y dimension before loading data".to_string()),
+            _ => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -115,29 +182,66 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
             self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
         }
 
-        for datum in data.iter() {
+        for (i, datum) in data.iter().enumerate() {
             let scaled_x = self.x_scale.unwrap().scale(&datum.get_x());
             let scaled_y = self.y_scale.unwrap().scale(&datum.get_y());
-            let y_bandwidth_offset = {
-                if self.y_scale.unwrap().is_range_reversed() {
-                    -self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
-                } else {
-                    self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
-                }
+            let y_bandwidth_offset = self.y_scale.unwrap().bandwidth().unwrap() / 2_f32;
+            let x_bandwidth_offset = self.x_scale.unwrap().bandwidth().unwrap() / 2_f32;
+            let marker_size = self.marker_size(datum.get_size());
+            let formatted_size = match (self.size_scale, datum.get_size()) {
+                (Some(size_scale), Some(size)) => Some(size_scale.format_tick(&size)),
+                _ => None,
             };
-            let x_bandwidth_offset = {
-                if self.x_scale.unwrap().is_range_reversed() {
-                    -self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
-                } else {
-                    self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
-                }
-            };
-            self.entries.push(ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, 5, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true, self.color_map.get(&datum.get_key()).unwrap().clone()));
+            self.entries.push(
+                ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, marker_size, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true, self.color_map.get(&datum.get_key()).unwrap().clone())
+                    .set_tooltip_visibility(self.tooltips_visible)
+                    .set_size(datum.get_size())
+                    .set_formatted_coordinates(self.x_scale.unwrap().format_tick(&datum.get_x()), self.y_scale.unwrap().format_tick(&datum.get_y()), formatted_size)
+                    .set_label_text(self.point_labels.get(i).cloned())
+            );
+        }
+
+        if self.dense_labels_hidden {
+            self.hide_overlapping_labels();
         }
 
         Ok(self)
     }
 
+    /// Return the radius of the marker of a datum with the given size.
+    fn marker_size(&self, size: Option<f32>) -> usize {
+        match (self.size_scale, size) {
+            (Some(size_scale), Some(size)) if size != 0_f32 => size_scale.scale(&size).round().max(0_f32) as usize,
+            _ => self.default_marker_size,
+        }
+    }
+
+    /// Hide the labels that overlap the label of a previous point that is still visible.
+    fn hide_overlapping_labels(&mut self) {
+        // The point labels use a larger font than the axes that the default measurer is meant for.
+        let measurer = AverageCharWidthMeasurer::new(8_f32);
+        let mut visible_bounds: Vec<(f32, f32, f32, f32)> = Vec::new();
+
+        for entry in std::mem::take(&mut self.entries) {
+            if !entry.is_label_visible() {
+                self.entries.push(entry);
+                continue;
+            }
+
+            let (left, top, right, bottom) = entry.label_bounds(&measurer);
+            let overlaps = visible_bounds.iter().any(|&(other_left, other_top, other_right, other_bottom)| {
+                left < other_right && other_left < right && top < other_bottom && other_top < bottom
+            });
+
+            if overlaps {
+                self.entries.push(entry.set_label_visibility(false));
+            } else {
+                visible_bounds.push((left, top, right, bottom));
+                self.entries.push(entry);
+            }
+        }
+    }
+
     /// Extract the list of keys to use when stacking and coloring the bars.
     fn extract_keys(data: &Vec<impl PointDatum<T, U>>) -> Vec<String> {
         let mut keys = Vec::new();
@@ -155,16 +259,63 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
 
 }
 
+impl<'a> ScatterView<'a, f32, f32> {
+    /// Return the least-squares fit of the data points, e.g. to label the trend line.
+    pub fn trend_line(&self) -> Option<LinearFit> {
+        let points = self.entries
+            .iter()
+            .map(|entry| (*entry.get_x_label(), *entry.get_y_label()))
+            .collect::<Vec<(f32, f32)>>();
+
+        linear_regression(&points)
+    }
+}
+
 impl<'a, T: Display, U: Display> View<'a> for ScatterView<'a, T, U> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new();
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let _ids = IdScope::enter();
+        let mut group = Group::new().set("class", view_class("scatter-view", &self.class));
 
         for entry in self.entries.iter() {
             let child_svg = entry.to_svg()?;
             group.append(child_svg);
         }
 
+        // The fit of the positions of the points is the fit of the data on linear scales.
+        if self.trend_line_visible {
+            let positions = self.entries.iter().map(|entry| (entry.get_x(), entry.get_y())).collect::<Vec<(f32, f32)>>();
+            if let (Some(fit), Some(x_scale)) = (linear_regression(&positions), self.x_scale) {
+                let (x1, x2) = (x_scale.range_start(), x_scale.range_end());
+                group.append(
+                    Line::new()
+                        .set("class", "trend-line")
+                        .set("x1", x1)
+                        .set("y1", fit.at(x1))
+                        .set("x2", x2)
+                        .set("y2", fit.at(x2))
+                        .set("stroke", "#333")
+                        .set("stroke-width", 2)
+                );
+            }
+        }
+
+        if self.clip_to_plot_area {
+            if let (Some(x_scale), Some(y_scale)) = (self.x_scale, self.y_scale) {
+                group = clip_to_plot_area(
+                    group,
+                    (x_scale.range_start(), x_scale.range_end()),
+                    (y_scale.range_start(), y_scale.range_end()),
+                );
+            }
+        }
+
         Ok(group)
     }
 
@@ -186,3 +337,224 @@ impl<'a, T: Display, U: Display> View<'a> for ScatterView<'a, T, U> {
         entries
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PowScale, ScaleLinear};
+
+    #[test]
+    fn clip_path_matches_plot_area() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let data = vec![(5_f32, 5_f32), (15_f32, 5_f32)];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_clip(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        let clip_id = svg.split("<clipPath id=\"").nth(1).unwrap().split('"').next().unwrap();
+        assert!(svg.contains(&format!("clip-path=\"url(#{})\"", clip_id)));
+        assert!(svg.contains("<rect height=\"400\" width=\"700\" x=\"0\" y=\"0\"/>"));
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!(!svg.contains("clipPath"));
+    }
+
+    #[test]
+    fn tooltips_list_the_coordinates() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let data = vec![(3, 7)];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_tooltip_visibility(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!(svg.contains("<title>\nx: 3, y: 7\n</title>"));
+    }
+
+    #[test]
+    fn bubble_tooltips_list_the_formatted_size() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700])
+            .set_tick_formatter(|tick| format!("{} km", tick));
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let sizes = PowScale::sqrt(vec![0_f32, 100_f32], vec![0, 20]);
+        let data = vec![(3_f32, 7_f32, 9_f32)];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_size_scale(&sizes)
+            .set_tooltip_visibility(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!(svg.contains("<title>\nx: 3 km, y: 7, size: 9\n</title>"));
+        assert!(svg.contains("aria-label=\"x: 3 km, y: 7, size: 9\""));
+
+        // Without a size scale, the size is still listed.
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_tooltip_visibility(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!(svg.contains("<title>\nx: 3 km, y: 7, size: 9\n</title>"));
+    }
+
+    #[test]
+    fn trend_line_of_points_near_double() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 500]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 20_f32])
+            .set_range(vec![400, 0]);
+        let data = vec![(1_f32, 2.1_f32), (2_f32, 3.9_f32), (3_f32, 6.2_f32), (4_f32, 7.8_f32), (5_f32, 10.1_f32)];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_trend_line_visibility(true)
+            .load_data(&data)
+            .unwrap();
+        let fit = view.trend_line().unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!((fit.slope - 2_f32).abs() < 0.05);
+        assert!(fit.intercept.abs() < 0.1);
+
+        let trend_line = svg.lines().find(|line| line.contains("class=\"trend-line\"")).unwrap();
+        assert!(trend_line.contains("x1=\"0\""));
+        assert!(trend_line.contains("x2=\"500\""));
+        let y1 = trend_line.split("y1=\"").nth(1).unwrap().split('"').next().unwrap().parse::<f32>().unwrap();
+        assert!((y1 - y.scale(&fit.intercept)).abs() < 0.01);
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        assert!(!view.to_svg().unwrap().to_string().contains("trend-line"));
+    }
+
+    #[test]
+    fn point_labels_beside_markers() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let data = vec![(1_f32, 2_f32), (5_f32, 5_f32), (9_f32, 8_f32)];
+        let names = vec![String::from("Austria"), String::from("Brazil"), String::from("Chile")];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_label_position(PointLabelPosition::E)
+            .set_point_labels(names.clone())
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let points = svg.split("class=\"scatter-point\"").skip(1).collect::<Vec<&str>>();
+
+        assert_eq!(points.len(), 3);
+        for ((point, (x_value, y_value)), name) in points.iter().zip(data.iter()).zip(names.iter()) {
+            assert!(point.contains(&format!("transform=\"translate({},{})\"", x.scale(x_value), y.scale(y_value))));
+            // The label starts past the marker radius of 5 pixels, at the height of its center.
+            assert!(point.contains(&format!("text-anchor=\"start\" x=\"13\" y=\"0\">\n{}\n</text>", name)));
+        }
+    }
+
+    #[test]
+    fn dense_point_labels_are_hidden() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let data = vec![(1_f32, 5_f32), (1.1_f32, 5_f32), (8_f32, 5_f32)];
+        let names = vec![String::from("Austria"), String::from("Belgium"), String::from("Chile")];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_point_labels(names)
+            .set_dense_label_hiding(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!(svg.contains("Austria"));
+        assert!(!svg.contains("Belgium"));
+        assert!(svg.contains("Chile"));
+    }
+
+    #[test]
+    fn bubbles_area_is_proportional_to_size() {
+        let x = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![0, 700]);
+        let y = ScaleLinear::new()
+            .set_domain(vec![0_f32, 10_f32])
+            .set_range(vec![400, 0]);
+        let sizes = PowScale::sqrt(vec![0_f32, 100_f32], vec![0, 20]);
+        let data = vec![(2_f32, 2_f32, 25_f32), (5_f32, 5_f32, 100_f32), (8_f32, 8_f32, 0_f32)];
+
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_size_scale(&sizes)
+            .set_default_marker_size(3)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let points = svg.split("class=\"scatter-point\"").skip(1).collect::<Vec<&str>>();
+
+        assert!(points[0].contains("r=\"10\""));
+        assert!(points[1].contains("r=\"20\""));
+        assert!(points[2].contains("r=\"3\""));
+
+        // Without a size scale, the sizes are ignored.
+        let view = ScatterView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert_eq!(svg.matches("r=\"5\"").count(), 3);
+    }
+}
@@ -1,27 +1,41 @@
 use std::collections::HashMap;
 use svg::node::Node;
 use svg::node::element::Group;
-use crate::components::bar::{Bar, BarBlock, BarLabelPosition};
+use crate::components::bar::{segment_connectors, Bar, BarBlock, BarLabelPosition, BarLayout};
 use crate::colors::Color;
 use crate::{Scale, BarDatum};
 use crate::scales::ScaleType;
 use crate::components::DatumRepresentation;
-use crate::views::View;
+use crate::views::{apply_drop_shadow, grouped_bar_slots, rendered_bar_width, CategoryEntries, view_class, View};
 use crate::chart::Orientation;
-use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::components::legend::{LegendEntry, LegendMarkerType, LegendValueMode};
+use crate::ChartError;
+use crate::components::IdScope;
 
 /// A View that represents data as vertical bars.
 pub struct VerticalBarView<'a> {
     label_position: BarLabelPosition,
+    layout: BarLayout,
     labels_visible: bool,
+    total_labels_visible: bool,
+    vertical_labels: bool,
+    segment_connectors_visible: bool,
+    shadow: bool,
+    legend_values: LegendValueMode,
+    key_totals: HashMap<String, f32>,
+    normalized: bool,
     rounding_precision: Option<usize>,
+    corner_radius: Option<f32>,
+    bar_width: Option<usize>,
     entries: Vec<Bar>,
     keys: Vec<String>,
     colors: Vec<Color>,
     color_map: HashMap<String, String>,
     x_scale: Option<&'a dyn Scale<String>>,
     y_scale: Option<&'a dyn Scale<f32>>,
+    opacity_scale: Option<&'a dyn Scale<f32>>,
     custom_data_label: String,
+    class: Option<String>,
 }
 
 impl<'a> VerticalBarView<'a> {
@@ -29,15 +43,27 @@ impl<'a> VerticalBarView<'a> {
     pub fn new() -> Self {
         Self {
             label_position: BarLabelPosition::EndOutside,
+            layout: BarLayout::Stacked,
             labels_visible: true,
+            total_labels_visible: false,
+            vertical_labels: false,
+            segment_connectors_visible: false,
+            shadow: false,
+            legend_values: LegendValueMode::None,
+            key_totals: HashMap::new(),
+            normalized: false,
             rounding_precision: None,
+            corner_radius: None,
+            bar_width: None,
             entries: Vec::new(),
             keys: Vec::new(),
             colors: Color::color_scheme_10(),
             color_map: HashMap::new(),
             x_scale: None,
             y_scale: None,
+            opacity_scale: None,
             custom_data_label: String::new(),
+            class: None,
         }
     }
 
@@ -53,6 +79,14 @@ impl<'a> VerticalBarView<'a> {
         self
     }
 
+    /// Set the scale that maps the confidence of each datum to the fill opacity of its bar.
+    /// The scale output is clamped between 0 and 1, e.g. a linear scale with a range of
+    /// `vec![0, 1]` maps confidences 0 to 1 directly onto opacities.
+    pub fn set_opacity_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
+        self.opacity_scale = Some(scale);
+        self
+    }
+
     /// Set the keys in case of a stacked bar chart.
     pub fn set_keys(mut self, keys: Vec<String>) -> Self {
         self.keys = keys;
@@ -65,6 +99,12 @@ impl<'a> VerticalBarView<'a> {
         self
     }
 
+    /// Set whether the keys of a category are stacked in a single bar, or placed side by side.
+    pub fn set_layout(mut self, layout: BarLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
     /// Set the color palette of the view.
     pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
         self.colors = colors;
@@ -77,6 +117,46 @@ impl<'a> VerticalBarView<'a> {
         self
     }
 
+    /// Set the visibility of the labels that display the total value of each stacked bar.
+    /// Grouped bars have no total, so loading data fails when both are set.
+    pub fn set_total_label_visibility(mut self, total_label_visibility: bool) -> Self {
+        self.total_labels_visible = total_label_visibility;
+        self
+    }
+
+    /// Set whether the labels should be rotated to run along the bars, which fits them inside narrow bars.
+    pub fn set_vertical_labels(mut self, vertical_labels: bool) -> Self {
+        self.vertical_labels = vertical_labels;
+        self
+    }
+
+    /// Set the visibility of the bands that connect the blocks of the same key
+    /// in adjacent bars of a stacked bar chart.
+    pub fn set_segment_connectors_visibility(mut self, connectors_visibility: bool) -> Self {
+        self.segment_connectors_visible = connectors_visibility;
+        self
+    }
+
+    /// Set whether each stacked bar should be scaled to the full height, with its blocks sized
+    /// and labeled by their percentage of the category total. The Y scale should then span 0 to 100.
+    pub fn set_normalized(mut self, normalized: bool) -> Self {
+        self.normalized = normalized;
+        self
+    }
+
+    /// Set whether the bars should cast a drop shadow.
+    pub fn set_shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    /// Set the value that is appended to the label of each legend entry: the total of the key,
+    /// or its share of the grand total.
+    pub fn set_legend_values(mut self, mode: LegendValueMode) -> Self {
+        self.legend_values = mode;
+        self
+    }
+
     /// Set custom label for the dataset.
     /// This will work when the dataset represents only a single
     /// type of data (i.e. there are no different "keys" by which to
@@ -92,15 +172,27 @@ impl<'a> VerticalBarView<'a> {
         self
     }
 
+    /// Set the radius of the rounded corners at the end of the bars.
+    pub fn set_corner_radius(mut self, corner_radius: f32) -> Self {
+        self.corner_radius = Some(corner_radius);
+        self
+    }
+
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl BarDatum>) -> Result<Self, String> {
+    pub fn load_data(mut self, data: &Vec<impl BarDatum>) -> Result<Self, ChartError> {
         match self.x_scale {
             Some(scale) if scale.get_type() == ScaleType::Band => {},
-            _ => return Err("The X axis scale should be a Band scale.".to_string()),
+            None => return Err(ChartError::MissingDimension(String::from("scale of the X dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the X scale should be a band scale"))),
         }
         match self.y_scale {
             Some(scale) if scale.get_type() == ScaleType::Linear => {},
-            _ => return Err("The Y axis scale should be a Linear scale.".to_string()),
+            None => return Err(ChartError::MissingDimension(String::from("scale of the Y dimension"))),
+            _ => return Err(ChartError::InvalidScale(String::from("the Y scale should be a linear scale"))),
+        }
+
+        if self.layout == BarLayout::Grouped && self.total_labels_visible {
+            return Err(ChartError::InvalidOption(String::from("total labels can only be shown for stacked bars")));
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -108,9 +200,14 @@ impl<'a> VerticalBarView<'a> {
             self.keys = Self::extract_keys(&data);
         }
 
+        // The position of each key within the band of a category, when the keys are grouped.
+        let key_indices = self.keys.iter().cloned().enumerate().map(|(i, key)| (key, i)).collect::<HashMap<String, usize>>();
+        let key_slots = grouped_bar_slots(&self.keys, self.x_scale.unwrap().bandwidth().unwrap());
+        self.bar_width = rendered_bar_width(self.layout, self.x_scale.unwrap().bandwidth().unwrap(), &key_slots);
+
         // HashMap to group all data related to a category. This is needed when there
         // are many data entries under a single category as in a stacked bar chart.
-        let mut categories: HashMap<String, Vec<(&String, f32)>> = HashMap::new();
+        let mut categories: CategoryEntries = HashMap::new();
 
         // Organize entries based on the order of the keys first, since displayed data
         // should keep the order defined in the `keys` attribute.
@@ -121,12 +218,13 @@ impl<'a> VerticalBarView<'a> {
             for entry in data.iter() {
                 if entry.get_key() == *key {
                     let entry_category = entry.get_category();
+                    *self.key_totals.entry(key.clone()).or_insert(0_f32) += entry.get_value();
 
                     if !categories.contains_key(&entry_category) {
                         categories.insert(entry.get_category(), Vec::new());
                     }
                     if let Some(category_entries) = categories.get_mut(&entry_category) {
-                        category_entries.push((key, entry.get_value()));
+                        category_entries.push((key, entry.get_value(), entry.get_confidence()));
                     }
                 }
             }
@@ -137,12 +235,46 @@ impl<'a> VerticalBarView<'a> {
         let y_range_is_reversed = self.y_scale.unwrap().is_range_reversed();
 
         for (category, key_value_pairs) in categories.iter_mut() {
+            if self.normalized {
+                let total = key_value_pairs.iter().map(|(_, value, _)| *value).sum::<f32>();
+                if total != 0_f32 {
+                    for (_, value, _) in key_value_pairs.iter_mut() {
+                        *value = *value / total * 100_f32;
+                    }
+                }
+            }
+
+            if self.layout == BarLayout::Grouped {
+                let x_scale = self.x_scale.unwrap();
+                let y_scale = self.y_scale.unwrap();
+                let zero = y_scale.scale(&0_f32);
+
+                for (key, value, confidence) in key_value_pairs.iter() {
+                    let position = y_scale.scale(value);
+                    let opacity = match (self.opacity_scale, confidence) {
+                        (Some(scale), Some(confidence)) => Some(scale.scale(confidence).clamp(0_f32, 1_f32)),
+                        _ => None,
+                    };
+                    let block = BarBlock::new(f32::min(zero, position), f32::max(zero, position), *value, self.color_map.get(*key).unwrap().clone(), key.to_string())
+                        .set_opacity(opacity);
+                    let (slot_offset, bar_width) = key_slots[key_indices[*key]];
+                    let offset = x_scale.scale(category) + slot_offset;
+
+                    bars.push(
+                        Bar::new(vec![block], Orientation::Vertical, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, bar_width, offset)
+                            .set_vertical_labels(self.vertical_labels)
+                            .set_corner_radius(self.corner_radius)
+                    );
+                }
+                continue;
+            }
+
             let mut value_acc = 0_f32;
             let mut bar_blocks = Vec::new();
             let mut stacked_start = self.y_scale.unwrap().scale(&value_acc);
             let mut stacked_end = stacked_start;
 
-            for (key, value) in key_value_pairs.iter() {
+            for (key, value, confidence) in key_value_pairs.iter() {
                 value_acc += *value;
                 // If Y axis' scale has the range in reversed order, then adjust the computation of
                 // the start and end positions to account for SVG coordinate system origin.
@@ -153,10 +285,20 @@ impl<'a> VerticalBarView<'a> {
                     stacked_start = stacked_end;
                     stacked_end = self.y_scale.unwrap().scale(&value_acc);
                 }
-                bar_blocks.push(BarBlock::new(stacked_start, stacked_end, *value, self.color_map.get(*key).unwrap().clone()));
+                let opacity = match (self.opacity_scale, confidence) {
+                    (Some(scale), Some(confidence)) => Some(scale.scale(confidence).clamp(0_f32, 1_f32)),
+                    _ => None,
+                };
+                bar_blocks.push(
+                    BarBlock::new(stacked_start, stacked_end, *value, self.color_map.get(*key).unwrap().clone(), key.to_string())
+                        .set_opacity(opacity)
+                );
             }
 
-            let bar = Bar::new(bar_blocks, Orientation::Vertical, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, self.x_scale.unwrap().bandwidth().unwrap(), self.x_scale.unwrap().scale(category));
+            let bar = Bar::new(bar_blocks, Orientation::Vertical, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, self.x_scale.unwrap().bandwidth().unwrap(), self.x_scale.unwrap().scale(category))
+                .set_total_label_visibility(self.total_labels_visible)
+                .set_vertical_labels(self.vertical_labels)
+                .set_corner_radius(self.corner_radius);
             bars.push(bar);
         }
 
@@ -167,6 +309,12 @@ impl<'a> VerticalBarView<'a> {
         Ok(self)
     }
 
+    /// The width of the bars as laid out by `load_data`, so that overlays can match it.
+    /// Grouped bars report the narrowest bar. `None` until the data is loaded.
+    pub fn rendered_bar_width(&self) -> Option<usize> {
+        self.bar_width
+    }
+
     /// Extract the list of keys to use when stacking and coloring the bars.
     fn extract_keys(data: &Vec<impl BarDatum>) -> Vec<String> {
         let mut keys = Vec::new();
@@ -190,33 +338,415 @@ impl<'a> VerticalBarView<'a> {
 }
 
 impl<'a> View<'a> for VerticalBarView<'a> {
+    /// Set a class to add to the group of the view, e.g. to style it with an external stylesheet.
+    fn set_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_owned());
+        self
+    }
+
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
-        let mut group = Group::new();
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let _ids = IdScope::enter();
+        let mut group = Group::new().set("class", view_class("vertical-bar-view", &self.class));
+
+        if self.segment_connectors_visible {
+            group.append(segment_connectors(&self.entries));
+        }
 
         for entry in self.entries.iter() {
             let child_svg = entry.to_svg()?;
             group.append(child_svg);
         }
 
+        if self.shadow {
+            group = apply_drop_shadow(group);
+        }
+
         Ok(group)
     }
 
     /// Return the legend entries that this view represents.
     fn get_legend_entries(&self) -> Vec<LegendEntry> {
         let mut entries = Vec::new();
+        let grand_total = self.key_totals.values().sum::<f32>();
+        let label = |key: &String, label: &str| {
+            self.legend_values.append_to(label, self.key_totals.get(key).cloned().unwrap_or(0_f32), grand_total)
+        };
 
         // If there is a single key and it is an empty string (meaning
         // the dataset consists only of X and Y dimension values), return
         // the custom data label.
         if self.keys.len() == 1 && self.keys[0].len() == 0 {
-            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), self.custom_data_label.clone()));
+            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), label(&self.keys[0], &self.custom_data_label)));
         } else {
             for key in self.keys.iter() {
-                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), key.clone()));
+                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), label(key, key)));
             }
         }
 
         entries
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::test_support::*;
+    use crate::{BarGroup, BarLabel, LogScale};
+
+    #[test]
+    fn segment_connectors_between_consecutive_categories() {
+        let x = band_scale(&["1977", "1978", "1979"], vec![0, 600]);
+        let y = linear_scale(0_f32, 250_f32, vec![400, 0]);
+        let data = vec![
+            ("1977", 36.9, "Cassette"),
+            ("1977", 127.3, "8 - Track"),
+            ("1978", 133.6, "8 - Track"),
+            ("1978", 61.3, "Cassette"),
+            ("1979", 102.3, "8 - Track"),
+        ];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_segment_connectors_visibility(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        // 1977 -> 1978 shares both keys, 1978 -> 1979 only shares "8 - Track".
+        assert_eq!(svg.matches("class=\"bar-connector\"").count(), 3);
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert_eq!(svg.matches("class=\"bar-connector\"").count(), 0);
+    }
+
+    #[test]
+    fn total_labels_on_top_of_stacked_bars() {
+        let x = band_scale(&["1977", "1978", "1979"], vec![0, 600]);
+        let y = linear_scale(0_f32, 250_000_000_f32, vec![500, 0]);
+        let data = vec![
+            ("1977", 36_900_000, "Cassette"),
+            ("1977", 127_300_000, "8 - Track"),
+            ("1978", 133_600_000, "8 - Track"),
+            ("1978", 61_300_000, "Cassette"),
+            ("1979", 102_300_000, "8 - Track"),
+        ];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_label_visibility(false)
+            .set_total_label_visibility(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert_eq!(svg.matches("class=\"bar-total\"").count(), 3);
+        let total_label_top = y.scale(&164_200_000_f32) - 16_f32;
+        assert!(svg.contains(&format!("y=\"{}\">\n164200000\n</text>", total_label_top)));
+    }
+
+    #[test]
+    fn bar_opacity_follows_confidence() {
+        let x = band_scale(&["A", "B", "C"], vec![0, 600]);
+        let y = linear_scale(0_f32, 100_f32, vec![400, 0]);
+        let opacity = linear_scale(0_f32, 1_f32, vec![0, 1]);
+        let data = vec![("A", 40_f32, 0.2_f32), ("B", 60_f32, 0.5_f32), ("C", 80_f32, 1_f32)];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_opacity_scale(&opacity)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let mut opacities = svg
+            .lines()
+            .filter(|line| line.starts_with("<rect"))
+            .map(|line| {
+                let height = attribute(line, "height");
+                let opacity = line.split("fill-opacity=\"").nth(1).unwrap().split('"').next().unwrap().to_owned();
+                (height as usize, opacity)
+            })
+            .collect::<Vec<(usize, String)>>();
+        opacities.sort();
+
+        assert_eq!(opacities, vec![
+            (160, String::from("0.2")),
+            (240, String::from("0.5")),
+            (320, String::from("1")),
+        ]);
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        assert!(!view.to_svg().unwrap().to_string().contains("fill-opacity"));
+    }
+
+    #[test]
+    fn vertical_labels_centered_in_narrow_bars() {
+        let x = band_scale(&["A", "B"], vec![0, 40]);
+        let y = linear_scale(0_f32, 1000_f32, vec![400, 0]);
+        let data = vec![("A", 500_f32), ("B", 1000_f32)];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_vertical_labels(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let lines = svg.lines().collect::<Vec<&str>>();
+        let labels = lines
+            .windows(2)
+            .filter(|pair| pair[0].starts_with("<text"))
+            .map(|pair| (pair[0], pair[1].parse::<f32>().unwrap()))
+            .collect::<Vec<(&str, f32)>>();
+        let center_x = x.bandwidth().unwrap() / 2_f32;
+
+        assert_eq!(labels.len(), 2);
+        for (label, value) in labels.iter() {
+            let center_y = (y.scale(value) + y.scale(&0_f32)) / 2_f32;
+            assert!(label.contains(&format!("transform=\"rotate(-90 {} {})\"", center_x, center_y)));
+            assert!(label.contains(&format!("x=\"{}\" y=\"{}\"", center_x, center_y)));
+            assert!(label.contains("text-anchor=\"middle\""));
+        }
+    }
+
+    #[test]
+    fn normalized_bars_fill_the_plot_height() {
+        let x = band_scale(&["1977", "1978", "1979"], vec![0, 600]);
+        let y = linear_scale(0_f32, 100_f32, vec![400, 0]);
+        let data = vec![
+            ("1977", 36.9, "Cassette"),
+            ("1977", 127.3, "8 - Track"),
+            ("1978", 133.6, "8 - Track"),
+            ("1978", 61.3, "Cassette"),
+            ("1979", 102.3, "8 - Track"),
+        ];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_normalized(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        let heights = svg
+            .lines()
+            .filter(|line| line.starts_with("<rect"))
+            .map(|line| attribute(line, "height"))
+            .collect::<Vec<f32>>();
+
+        assert_eq!(heights.len(), 5);
+        assert!((heights.iter().sum::<f32>() - 3_f32 * 400_f32).abs() < 1e-2);
+        assert!(heights.contains(&400_f32));
+        assert!(svg.contains(">\n100\n</text>"));
+    }
+
+    #[test]
+    fn shadow_filter_is_defined_and_referenced() {
+        let x = band_scale(&["A", "B"], vec![0, 200]);
+        let y = linear_scale(0_f32, 100_f32, vec![400, 0]);
+        let data = vec![("A", 40_f32), ("B", 60_f32)];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_shadow(true)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        let filter_id = svg.split("<filter height=\"140%\" id=\"").nth(1).unwrap().split('"').next().unwrap();
+        assert!(svg.contains("<feDropShadow dx=\"2\" dy=\"2\" flood-opacity=\"0.3\" stdDeviation=\"2\"/>"));
+        assert!(svg.contains(&format!("<g class=\"vertical-bar-view\" filter=\"url(#{})\">", filter_id)));
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        assert!(!view.to_svg().unwrap().to_string().contains("filter"));
+    }
+
+    #[test]
+    fn legend_entries_with_values() {
+        let x = band_scale(&["1977", "1978", "1979"], vec![0, 600]);
+        let y = linear_scale(0_f32, 250_000_000_f32, vec![400, 0]);
+        let data = vec![
+            ("1977", 36_900_000, "Cassette"),
+            ("1977", 127_300_000, "8 - Track"),
+            ("1978", 133_600_000, "8 - Track"),
+            ("1978", 61_300_000, "Cassette"),
+            ("1979", 102_300_000, "8 - Track"),
+        ];
+        let legend_labels = |mode: LegendValueMode| {
+            VerticalBarView::new()
+                .set_x_scale(&x)
+                .set_y_scale(&y)
+                .set_legend_values(mode)
+                .load_data(&data)
+                .unwrap()
+                .get_legend_entries()
+                .iter()
+                .map(|entry| entry.to_svg().unwrap().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        let percentages = legend_labels(LegendValueMode::Percentage);
+        assert!(percentages[0].contains(">\nCassette (21%)\n</text>"));
+        assert!(percentages[1].contains(">\n8 - Track (79%)\n</text>"));
+
+        let totals = legend_labels(LegendValueMode::Absolute);
+        assert!(totals[0].contains(">\nCassette (98.2M)\n</text>"));
+        assert!(totals[1].contains(">\n8 - Track (363.2M)\n</text>"));
+
+        let plain = legend_labels(LegendValueMode::None);
+        assert!(plain[0].contains(">\nCassette\n</text>"));
+    }
+
+    #[test]
+    fn view_group_has_stable_and_custom_class() {
+        let x = band_scale(&["A", "B"], vec![0, 200]);
+        let y = linear_scale(0_f32, 100_f32, vec![400, 0]);
+        let data = vec![("A", 40_f32), ("B", 60_f32)];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+        assert!(svg.starts_with("<g class=\"vertical-bar-view\">"));
+        assert!(svg.contains("class=\"bar\""));
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_class("revenue")
+            .load_data(&data)
+            .unwrap();
+        assert!(view.to_svg().unwrap().to_string().starts_with("<g class=\"vertical-bar-view revenue\">"));
+    }
+
+    #[test]
+    fn bars_are_described_for_screen_readers() {
+        let x = band_scale(&["A", "B"], vec![0, 200]);
+        let y = linear_scale(0_f32, 100_f32, vec![400, 0]);
+        let data = vec![("A", 40_f32, "Cassette"), ("B", 60_f32, "Vinyl")];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_keys(vec![String::from("Cassette"), String::from("Vinyl")])
+            .load_data(&data)
+            .unwrap();
+        let svg = view.to_svg().unwrap().to_string();
+
+        assert!(svg.contains("<title>\nB, Vinyl: 60\n</title>"));
+        assert!(svg.contains("aria-label=\"A, Cassette: 40\""));
+    }
+
+    #[test]
+    fn grouped_keys_share_the_band() {
+        let x = band_scale(&["1977", "1978"], vec![0, 600]);
+        let y = linear_scale(0_f32, 200_f32, vec![400, 0]);
+        let data = vec![
+            ("1977", 100_f32, "Cassette"),
+            ("1977", 150_f32, "8 - Track"),
+        ];
+
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_label_visibility(false)
+            .set_layout(BarLayout::Grouped)
+            .load_data(&data)
+            .unwrap();
+        let bars = bar_blocks(&view.to_svg().unwrap().to_string());
+
+        assert_eq!(bars.len(), 2);
+        let (first, second) = (bars[0], bars[1]);
+        let band_start = x.scale(&String::from("1977"));
+
+        assert_eq!(first.0.0, band_start);
+        assert_eq!(second.0.0, band_start + first.1);
+        assert_eq!(first.1 + second.1, x.bandwidth().unwrap());
+        assert_eq!(first.2, 200_f32);
+        assert_eq!(second.2, 300_f32);
+    }
+
+    #[test]
+    fn rendered_bar_width_matches_the_bar_group() {
+        let years = (1967..=1974).map(|year| year.to_string()).collect::<Vec<String>>();
+        let x = band_scale(&years.iter().map(String::as_str).collect::<Vec<&str>>(), vec![0, 800])
+            .set_inner_padding(0_f32)
+            .set_outer_padding(0_f32);
+        let y = linear_scale(0_f32, 200_f32, vec![400, 0]);
+        let data = years.iter().map(|year| (year.as_str(), 100_f32, "Cassette")).collect::<Vec<_>>();
+        let group = BarGroup::new("years").define_labels((1967..=1974).map(BarLabel::from));
+
+        let view = VerticalBarView::new().set_x_scale(&x).set_y_scale(&y);
+        assert_eq!(view.rendered_bar_width(), None);
+
+        let view = view.load_data(&data).unwrap();
+        assert_eq!(view.rendered_bar_width(), Some(group.calculate_bar_width(800)));
+        assert_eq!(view.rendered_bar_width(), Some(100));
+
+        // Grouped keys divide the band, the leftover pixel widens the first bar.
+        let keys = BarGroup::new("keys").define_labels((0..3).map(BarLabel::from));
+        let data = vec![("1967", 100_f32, "Cassette"), ("1967", 150_f32, "8 - Track"), ("1967", 50_f32, "LP/EP")];
+        let view = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_layout(BarLayout::Grouped)
+            .load_data(&data)
+            .unwrap();
+        assert_eq!(view.rendered_bar_width(), Some(keys.calculate_bar_width(100)));
+        assert_eq!(view.rendered_bar_width(), Some(33));
+    }
+
+    #[test]
+    fn grouped_bars_have_no_total_labels() {
+        let x = band_scale(&["1977"], vec![0, 600]);
+        let y = linear_scale(0_f32, 200_f32, vec![400, 0]);
+        let data = vec![("1977", 100_f32, "Cassette"), ("1977", 150_f32, "8 - Track")];
+
+        let result = VerticalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_layout(BarLayout::Grouped)
+            .set_total_label_visibility(true)
+            .load_data(&data);
+
+        assert_eq!(result.err(), Some(ChartError::InvalidOption(String::from("total labels can only be shown for stacked bars"))));
+    }
+
+    #[test]
+    fn scales_that_do_not_suit_the_bars_are_invalid() {
+        let x = band_scale(&["A"], vec![0, 200]);
+        let y = LogScale::new(400_f32..=0_f32, 1_f32, 100_f32).unwrap();
+        let data = vec![("A", 40_f32, "Cassette")];
+
+        let result = VerticalBarView::new().set_y_scale(&y).load_data(&data);
+        assert_eq!(result.err(), Some(ChartError::MissingDimension(String::from("scale of the X dimension"))));
+
+        let result = VerticalBarView::new().set_x_scale(&x).set_y_scale(&y).load_data(&data);
+        assert!(matches!(result.err(), Some(ChartError::InvalidScale(_))));
+
+        // Code that reports errors as text keeps working through the conversion to a message.
+        let load = || -> Result<VerticalBarView, String> { Ok(VerticalBarView::new().set_x_scale(&x).set_y_scale(&y).load_data(&data)?) };
+        assert_eq!(load().err(), Some(String::from("Invalid scale: the Y scale should be a linear scale")));
+    }
+
+}